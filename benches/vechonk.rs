@@ -0,0 +1,427 @@
+//! Compares `Vechonk` with the usual ways of storing unsized values: `Vec<Box<str>>`, `Vec<String>` and
+//! `Vec<Box<dyn Trait>>`. Run with `cargo bench`, or `cargo bench -- <group>` for a single group.
+//!
+//! The `dyn` workloads mix elements of different sizes and alignments, including zero sized and over-aligned ones,
+//! so they include the cost of padding.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use vechonk::Vechonk;
+
+const LEN: usize = 10_000;
+
+trait Shape {
+    fn value(&self) -> u64;
+
+    fn clone_box(&self) -> Box<dyn Shape>;
+}
+
+struct Zst;
+
+#[repr(align(64))]
+struct Align64(u64);
+
+impl Shape for Zst {
+    fn value(&self) -> u64 {
+        0
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(Zst)
+    }
+}
+
+impl Shape for u8 {
+    fn value(&self) -> u64 {
+        u64::from(*self)
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+}
+
+impl Shape for u64 {
+    fn value(&self) -> u64 {
+        *self
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+}
+
+impl Shape for [u16; 3] {
+    fn value(&self) -> u64 {
+        self.iter().map(|&word| u64::from(word)).sum()
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+}
+
+impl Shape for Align64 {
+    fn value(&self) -> u64 {
+        self.0
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(Align64(self.0))
+    }
+}
+
+/// A xorshift generator, so that all runs use the same data
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, max: usize) -> usize {
+        (self.next() % max as u64) as usize
+    }
+}
+
+fn strings() -> Vec<String> {
+    let mut rng = Rng(0x5eed);
+
+    (0..LEN)
+        .map(|_| {
+            let len = rng.below(32);
+            (0..len)
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect()
+}
+
+fn shapes() -> Vec<Box<dyn Shape>> {
+    let mut rng = Rng(0x5eed);
+
+    (0..LEN)
+        .map(|_| -> Box<dyn Shape> {
+            let value = rng.next();
+            match rng.below(5) {
+                0 => Box::new(Zst),
+                1 => Box::new(value as u8),
+                2 => Box::new(value),
+                3 => Box::new([value as u16; 3]),
+                _ => Box::new(Align64(value)),
+            }
+        })
+        .collect()
+}
+
+fn boxed_strs(strings: &[String]) -> Vec<Box<str>> {
+    strings.iter().map(|s| s.as_str().into()).collect()
+}
+
+fn random_indices() -> Vec<usize> {
+    let mut rng = Rng(0xd1ce);
+    (0..LEN).map(|_| rng.below(LEN)).collect()
+}
+
+fn str_chonk(strings: &[String]) -> Vechonk<str> {
+    let mut chonk = Vechonk::new();
+    strings.iter().for_each(|s| chonk.push(s.as_str().into()));
+    chonk
+}
+
+fn dyn_chonk(shapes: &[Box<dyn Shape>]) -> Vechonk<dyn Shape> {
+    let mut chonk: Vechonk<dyn Shape> = Vechonk::new();
+    shapes
+        .iter()
+        .for_each(|shape| chonk.push(shape.clone_box()));
+    chonk
+}
+
+fn clone_shapes(shapes: &[Box<dyn Shape>]) -> Vec<Box<dyn Shape>> {
+    shapes.iter().map(|shape| shape.clone_box()).collect()
+}
+
+/// Pushing into a buffer that is big enough, without any regrowing
+fn push(c: &mut Criterion) {
+    let strings = strings();
+    let shapes = shapes();
+    let str_bytes = strings.iter().map(String::len).sum();
+    let dyn_bytes = shapes
+        .iter()
+        .map(|shape| std::mem::size_of_val(&**shape))
+        .sum();
+    let mut group = c.benchmark_group("push");
+
+    group.bench_function("str/Vechonk", |b| {
+        b.iter_batched(
+            || boxed_strs(&strings),
+            |input| {
+                let mut chonk: Vechonk<str> = Vechonk::with_capacity_for(LEN, str_bytes);
+                input.into_iter().for_each(|s| chonk.push(s));
+                chonk
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("str/Vec<Box<str>>", |b| {
+        b.iter_batched(
+            || boxed_strs(&strings),
+            |input| {
+                let mut vec = Vec::with_capacity(LEN);
+                input.into_iter().for_each(|s| vec.push(s));
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("str/Vec<String>", |b| {
+        b.iter_batched(
+            || strings.clone(),
+            |input| {
+                let mut vec = Vec::with_capacity(LEN);
+                input.into_iter().for_each(|s| vec.push(s));
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dyn/Vechonk", |b| {
+        b.iter_batched(
+            || clone_shapes(&shapes),
+            |input| {
+                let mut chonk: Vechonk<dyn Shape> = Vechonk::with_capacity_for(LEN, dyn_bytes);
+                input.into_iter().for_each(|shape| chonk.push(shape));
+                chonk
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dyn/Vec<Box<dyn Trait>>", |b| {
+        b.iter_batched(
+            || clone_shapes(&shapes),
+            |input| {
+                let mut vec = Vec::with_capacity(LEN);
+                input.into_iter().for_each(|shape| vec.push(shape));
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+/// Pushing into an empty collection, so the buffer regrows along the way.
+/// The difference to `push` is the cost of regrowing
+fn regrow(c: &mut Criterion) {
+    let strings = strings();
+    let shapes = shapes();
+    let mut group = c.benchmark_group("regrow");
+
+    group.bench_function("str/Vechonk", |b| {
+        b.iter_batched(
+            || boxed_strs(&strings),
+            |input| {
+                let mut chonk: Vechonk<str> = Vechonk::new();
+                input.into_iter().for_each(|s| chonk.push(s));
+                chonk
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("str/Vec<Box<str>>", |b| {
+        b.iter_batched(
+            || boxed_strs(&strings),
+            |input| {
+                let mut vec = Vec::new();
+                input.into_iter().for_each(|s| vec.push(s));
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("str/Vec<String>", |b| {
+        b.iter_batched(
+            || strings.clone(),
+            |input| {
+                let mut vec = Vec::new();
+                input.into_iter().for_each(|s| vec.push(s));
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dyn/Vechonk", |b| {
+        b.iter_batched(
+            || clone_shapes(&shapes),
+            |input| {
+                let mut chonk: Vechonk<dyn Shape> = Vechonk::new();
+                input.into_iter().for_each(|shape| chonk.push(shape));
+                chonk
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dyn/Vec<Box<dyn Trait>>", |b| {
+        b.iter_batched(
+            || clone_shapes(&shapes),
+            |input| {
+                let mut vec = Vec::new();
+                input.into_iter().for_each(|shape| vec.push(shape));
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+/// Looking up elements at random indices
+fn get_random(c: &mut Criterion) {
+    let strings = strings();
+    let shapes = shapes();
+    let indices = random_indices();
+    let mut group = c.benchmark_group("get_random");
+
+    let chonk = str_chonk(&strings);
+    group.bench_function("str/Vechonk", |b| {
+        b.iter(|| indices.iter().map(|&i| chonk[i].len()).sum::<usize>())
+    });
+    let boxed = boxed_strs(&strings);
+    group.bench_function("str/Vec<Box<str>>", |b| {
+        b.iter(|| indices.iter().map(|&i| boxed[i].len()).sum::<usize>())
+    });
+    group.bench_function("str/Vec<String>", |b| {
+        b.iter(|| indices.iter().map(|&i| strings[i].len()).sum::<usize>())
+    });
+
+    let chonk = dyn_chonk(&shapes);
+    group.bench_function("dyn/Vechonk", |b| {
+        b.iter(|| indices.iter().map(|&i| chonk[i].value()).sum::<u64>())
+    });
+    group.bench_function("dyn/Vec<Box<dyn Trait>>", |b| {
+        b.iter(|| indices.iter().map(|&i| shapes[i].value()).sum::<u64>())
+    });
+
+    group.finish();
+}
+
+/// Visiting all elements in order
+fn iter(c: &mut Criterion) {
+    let strings = strings();
+    let shapes = shapes();
+    let mut group = c.benchmark_group("iter");
+
+    let chonk = str_chonk(&strings);
+    group.bench_function("str/Vechonk", |b| {
+        b.iter(|| chonk.iter().map(str::len).sum::<usize>())
+    });
+    let boxed = boxed_strs(&strings);
+    group.bench_function("str/Vec<Box<str>>", |b| {
+        b.iter(|| boxed.iter().map(|s| s.len()).sum::<usize>())
+    });
+    group.bench_function("str/Vec<String>", |b| {
+        b.iter(|| strings.iter().map(String::len).sum::<usize>())
+    });
+
+    let chonk = dyn_chonk(&shapes);
+    group.bench_function("dyn/Vechonk", |b| {
+        b.iter(|| chonk.iter().map(Shape::value).sum::<u64>())
+    });
+    group.bench_function("dyn/Vec<Box<dyn Trait>>", |b| {
+        b.iter(|| shapes.iter().map(|shape| shape.value()).sum::<u64>())
+    });
+
+    group.finish();
+}
+
+/// Consuming the collection and taking ownership of every element.
+/// `Vechonk` has to allocate a `Box` for each element, while the others just move theirs out
+fn into_iter(c: &mut Criterion) {
+    let strings = strings();
+    let shapes = shapes();
+    let mut group = c.benchmark_group("into_iter");
+
+    group.bench_function("str/Vechonk", |b| {
+        b.iter_batched(
+            || str_chonk(&strings),
+            |chonk| chonk.into_iter().map(|s| black_box(s).len()).sum::<usize>(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("str/Vec<Box<str>>", |b| {
+        b.iter_batched(
+            || boxed_strs(&strings),
+            |vec| vec.into_iter().map(|s| black_box(s).len()).sum::<usize>(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("str/Vec<String>", |b| {
+        b.iter_batched(
+            || strings.clone(),
+            |vec| vec.into_iter().map(|s| black_box(s).len()).sum::<usize>(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dyn/Vechonk", |b| {
+        b.iter_batched(
+            || dyn_chonk(&shapes),
+            |chonk| {
+                chonk
+                    .into_iter()
+                    .map(|shape| black_box(shape).value())
+                    .sum::<u64>()
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dyn/Vec<Box<dyn Trait>>", |b| {
+        b.iter_batched(
+            || clone_shapes(&shapes),
+            |vec| {
+                vec.into_iter()
+                    .map(|shape| black_box(shape).value())
+                    .sum::<u64>()
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+/// Pushing `n` elements into an empty `Vechonk`, to see how regrowing scales with the length
+fn regrow_scaling(c: &mut Criterion) {
+    let shapes = shapes();
+    let mut group = c.benchmark_group("regrow_scaling");
+
+    for len in [100, 1_000, 10_000] {
+        group.bench_with_input(BenchmarkId::new("dyn/Vechonk", len), &len, |b, &len| {
+            b.iter_batched(
+                || clone_shapes(&shapes[..len]),
+                |input| {
+                    let mut chonk: Vechonk<dyn Shape> = Vechonk::new();
+                    input.into_iter().for_each(|shape| chonk.push(shape));
+                    chonk
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    push,
+    regrow,
+    regrow_scaling,
+    get_random,
+    iter,
+    into_iter
+);
+criterion_main!(benches);
@@ -0,0 +1,12 @@
+fn main() {
+    println!("cargo:rerun-if-changed=test.c");
+    println!("cargo:rerun-if-changed=../include/vechonk.h");
+
+    cc::Build::new()
+        .file("test.c")
+        .include("../include")
+        .warnings(true)
+        .extra_warnings(true)
+        .warnings_into_errors(true)
+        .compile("vechonk_c_test");
+}
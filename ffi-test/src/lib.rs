@@ -0,0 +1,18 @@
+//! Runs the C test program in `test.c` against the `ffi` module of `vechonk`
+
+#[cfg(test)]
+mod test {
+    // the C code only references `vechonk` by symbol names, so make sure that it gets linked
+    extern crate vechonk;
+
+    extern "C" {
+        fn vechonk_c_test() -> i32;
+    }
+
+    #[test]
+    fn c_test_program() {
+        // SAFETY: The C test only uses the API as documented
+        let failed_line = unsafe { vechonk_c_test() };
+        assert_eq!(failed_line, 0, "C check failed on line {}", failed_line);
+    }
+}
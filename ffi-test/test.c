@@ -0,0 +1,108 @@
+#include <string.h>
+
+#include "vechonk.h"
+
+/* returns from the test with the current line if the condition doesn't hold */
+#define CHECK(cond)      \
+    do {                 \
+        if (!(cond)) {   \
+            return __LINE__; \
+        }                \
+    } while (0)
+
+static int slice_eq(VechonkSlice slice, const char *expected) {
+    size_t len = strlen(expected);
+    return slice.len == len && (len == 0 || memcmp(slice.ptr, expected, len) == 0);
+}
+
+static int test_str(void) {
+    const char *strings[] = {"hello", "", "world", "\xc3\xa4pfel"};
+    const size_t count = sizeof(strings) / sizeof(strings[0]);
+    const uint8_t invalid[] = {0xff, 0xfe};
+    VechonkSlice slice;
+    VechonkIter iter;
+    size_t i;
+
+    VechonkStr *chonk = vechonk_str_new();
+    CHECK(chonk != NULL);
+    CHECK(vechonk_str_len(chonk) == 0);
+    CHECK(!vechonk_str_get(chonk, 0, &slice));
+
+    for (i = 0; i < count; i++) {
+        CHECK(vechonk_str_push(chonk, (const uint8_t *)strings[i], strlen(strings[i])));
+    }
+    CHECK(!vechonk_str_push(chonk, invalid, sizeof(invalid)));
+    CHECK(vechonk_str_push(chonk, NULL, 0));
+    CHECK(vechonk_str_len(chonk) == count + 1);
+
+    for (i = 0; i < count; i++) {
+        CHECK(vechonk_str_get(chonk, i, &slice));
+        CHECK(slice_eq(slice, strings[i]));
+    }
+    CHECK(vechonk_str_get(chonk, count, NULL));
+    CHECK(!vechonk_str_get(chonk, count + 1, &slice));
+
+    iter = vechonk_str_iter(chonk);
+    for (i = 0; vechonk_str_iter_next(&iter, &slice); i++) {
+        CHECK(i <= count);
+        CHECK(slice_eq(slice, i < count ? strings[i] : ""));
+    }
+    CHECK(i == count + 1);
+    CHECK(!vechonk_str_iter_next(&iter, &slice));
+
+    vechonk_str_free(chonk);
+    vechonk_str_free(NULL);
+    return 0;
+}
+
+static int test_bytes(void) {
+    const uint8_t first[] = {0, 1, 2, 3};
+    const uint8_t second[] = {0xff, 0x00, 0xfe};
+    VechonkSlice slice;
+    VechonkIter iter;
+    size_t i;
+
+    VechonkBytes *chonk = vechonk_bytes_new();
+    CHECK(chonk != NULL);
+
+    CHECK(vechonk_bytes_push(chonk, first, sizeof(first)));
+    CHECK(vechonk_bytes_push(chonk, second, sizeof(second)));
+    CHECK(vechonk_bytes_push(chonk, NULL, 0));
+    CHECK(vechonk_bytes_len(chonk) == 3);
+
+    CHECK(vechonk_bytes_get(chonk, 0, &slice));
+    CHECK(slice.len == sizeof(first) && memcmp(slice.ptr, first, sizeof(first)) == 0);
+    CHECK(vechonk_bytes_get(chonk, 1, &slice));
+    CHECK(slice.len == sizeof(second) && memcmp(slice.ptr, second, sizeof(second)) == 0);
+    CHECK(vechonk_bytes_get(chonk, 2, &slice));
+    CHECK(slice.len == 0);
+    CHECK(!vechonk_bytes_get(chonk, 3, &slice));
+
+    /* many pushes, so the buffer has to grow a few times */
+    for (i = 0; i < 1000; i++) {
+        uint8_t byte = (uint8_t)i;
+        CHECK(vechonk_bytes_push(chonk, &byte, 1));
+    }
+    CHECK(vechonk_bytes_len(chonk) == 1003);
+
+    iter = vechonk_bytes_iter(chonk);
+    for (i = 0; vechonk_bytes_iter_next(&iter, &slice); i++) {
+        if (i >= 3) {
+            CHECK(slice.len == 1 && slice.ptr[0] == (uint8_t)(i - 3));
+        }
+    }
+    CHECK(i == 1003);
+
+    vechonk_bytes_free(chonk);
+    vechonk_bytes_free(NULL);
+    return 0;
+}
+
+/* returns 0 on success, and the line of the failed check otherwise */
+int vechonk_c_test(void) {
+    int failed = test_str();
+    if (failed != 0) {
+        return failed;
+    }
+    return test_bytes();
+}
@@ -0,0 +1,110 @@
+#ifndef VECHONK_H
+#define VECHONK_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// A `Vechonk<[u8]>`, containing arbitrary bytes
+typedef struct VechonkBytes VechonkBytes;
+
+// A `Vechonk<str>`, containing UTF-8 strings
+typedef struct VechonkStr VechonkStr;
+
+// A borrowed element, which is not null terminated
+typedef struct VechonkSlice {
+  const uint8_t *ptr;
+  uintptr_t len;
+} VechonkSlice;
+
+// An iterator over the elements of a `VechonkStr` or `VechonkBytes`, that lives on the C side.
+// It must only be used with the `Vechonk` it was created from, and only as long as that isn't pushed to or freed
+typedef struct VechonkIter {
+  const void *chonk;
+  uintptr_t index;
+} VechonkIter;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a new empty `VechonkStr`, which has to be freed with `vechonk_str_free`
+VechonkStr *vechonk_str_new(void);
+
+// Frees a `VechonkStr` and all of its strings. Does nothing if `chonk` is null
+//
+// # Safety
+// `chonk` must be null or come from `vechonk_str_new`, and must not be used afterwards
+void vechonk_str_free(VechonkStr *chonk);
+
+// Copies `len` bytes from `ptr` into a new string at the end. Returns false and pushes nothing
+// if the bytes are not valid UTF-8, or false if it panicked
+//
+// # Safety
+// `chonk` must be valid, and `ptr` must be valid for reads of `len` bytes, or `len` must be 0
+bool vechonk_str_push(VechonkStr *chonk, const uint8_t *ptr, uintptr_t len);
+
+// The amount of strings
+//
+// # Safety
+// `chonk` must be valid
+uintptr_t vechonk_str_len(const VechonkStr *chonk);
+
+// Writes the string at `index` to `out`. Returns false and leaves `out` untouched if the index is out of bounds
+//
+// # Safety
+// `chonk` must be valid, and `out` must be null or valid for writes
+bool vechonk_str_get(const VechonkStr *chonk, uintptr_t index, VechonkSlice *out);
+
+// Creates an iterator over the strings, starting at the first one
+VechonkIter vechonk_str_iter(const VechonkStr *chonk);
+
+// Writes the next string to `out` and advances the iterator. Returns false once all strings have been visited
+//
+// # Safety
+// `iter` must be valid and come from `vechonk_str_iter`, and `out` must be null or valid for writes
+bool vechonk_str_iter_next(VechonkIter *iter, VechonkSlice *out);
+
+// Creates a new empty `VechonkBytes`, which has to be freed with `vechonk_bytes_free`
+VechonkBytes *vechonk_bytes_new(void);
+
+// Frees a `VechonkBytes` and all of its elements. Does nothing if `chonk` is null
+//
+// # Safety
+// `chonk` must be null or come from `vechonk_bytes_new`, and must not be used afterwards
+void vechonk_bytes_free(VechonkBytes *chonk);
+
+// Copies `len` bytes from `ptr` into a new element at the end. Returns false if it panicked
+//
+// # Safety
+// `chonk` must be valid, and `ptr` must be valid for reads of `len` bytes, or `len` must be 0
+bool vechonk_bytes_push(VechonkBytes *chonk, const uint8_t *ptr, uintptr_t len);
+
+// The amount of elements
+//
+// # Safety
+// `chonk` must be valid
+uintptr_t vechonk_bytes_len(const VechonkBytes *chonk);
+
+// Writes the element at `index` to `out`. Returns false and leaves `out` untouched if the index is out of bounds
+//
+// # Safety
+// `chonk` must be valid, and `out` must be null or valid for writes
+bool vechonk_bytes_get(const VechonkBytes *chonk, uintptr_t index, VechonkSlice *out);
+
+// Creates an iterator over the elements, starting at the first one
+VechonkIter vechonk_bytes_iter(const VechonkBytes *chonk);
+
+// Writes the next element to `out` and advances the iterator. Returns false once all elements have been visited
+//
+// # Safety
+// `iter` must be valid and come from `vechonk_bytes_iter`, and `out` must be null or valid for writes
+bool vechonk_bytes_iter_next(VechonkIter *iter, VechonkSlice *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* VECHONK_H */
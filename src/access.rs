@@ -0,0 +1,98 @@
+//! Reading the elements, shared by every front end that lays them out like a `Vechonk`
+
+use crate::raw::RawVechonk;
+
+/// Gives access to the `RawVechonk` viewing the elements of a front end, so that everything that only reads
+/// or overwrites the elements is implemented once by [`impl_access`]
+///
+/// # Safety
+/// The `RawVechonk`s must view `len` valid elements, that stay valid for as long as `self` is borrowed.
+/// The one from `raw_view_mut` is written through, so it must not be derived from a shared reference
+pub(crate) unsafe trait RawAccess<T: ?Sized> {
+    /// A shallow copy of the `RawVechonk`, only to be used for reading
+    fn raw_view(&self) -> RawVechonk<T>;
+
+    /// A shallow copy of the `RawVechonk` that can be written through. Changes to its length are not written back
+    fn raw_view_mut(&mut self) -> RawVechonk<T>;
+}
+
+/// Implements `is_empty`, `get`, `get_mut`, `iter`, `iter_mut`, `Index`, `IndexMut` and `IntoIterator` for
+/// references on a front end that implements [`RawAccess`]. The front end needs a `const fn len` of its own,
+/// since trait methods can't be called in a `const fn`
+macro_rules! impl_access {
+    ([$($generics:tt)*] $ty:ty, $name:literal) => {
+        impl<$($generics)*> $ty {
+            #[doc = concat!("Whether the `", $name, "` is empty, O(1)")]
+            pub const fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// An iterator over the elements yielding shared references
+            pub fn iter(&self) -> $crate::Iter<'_, T> {
+                $crate::Iter::from_raw($crate::access::RawAccess::raw_view(self))
+            }
+
+            /// An iterator over the elements yielding mutable references
+            pub fn iter_mut(&mut self) -> $crate::IterMut<'_, T> {
+                $crate::IterMut::from_raw($crate::access::RawAccess::raw_view_mut(self))
+            }
+
+            /// Get a reference to an element at the index. Returns `None` if the index is out of bounds
+            pub fn get(&self, index: usize) -> Option<&T> {
+                let raw = $crate::access::RawAccess::raw_view(self);
+
+                if index < raw.len {
+                    // SAFETY: The index has been checked above, and the element is borrowed from `self`
+                    unsafe { Some(&*raw.get_unchecked_ptr(index)) }
+                } else {
+                    None
+                }
+            }
+
+            /// Get a mutable reference to an element at the index. Returns `None` if the index is out of bounds
+            pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+                let raw = $crate::access::RawAccess::raw_view_mut(self);
+
+                if index < raw.len {
+                    // SAFETY: The index has been checked above, and the element is borrowed mutably from `self`
+                    unsafe { Some(&mut *raw.get_unchecked_ptr(index)) }
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<$($generics)*> core::ops::Index<usize> for $ty {
+            type Output = T;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                let raw = $crate::access::RawAccess::raw_view(self);
+                raw.check_index(index);
+
+                // SAFETY: The index is not out of bounds, and the element is borrowed from `self`
+                unsafe { &*raw.get_unchecked_ptr(index) }
+            }
+        }
+
+        impl<$($generics)*> core::ops::IndexMut<usize> for $ty {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                let raw = $crate::access::RawAccess::raw_view_mut(self);
+                raw.check_index(index);
+
+                // SAFETY: The index is not out of bounds, and the element is borrowed mutably from `self`
+                unsafe { &mut *raw.get_unchecked_ptr(index) }
+            }
+        }
+
+        impl<'r, $($generics)*> IntoIterator for &'r $ty {
+            type Item = &'r T;
+            type IntoIter = $crate::Iter<'r, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_access;
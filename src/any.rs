@@ -0,0 +1,63 @@
+//! Helpers for using a `Vechonk<dyn Any>` as a bag of values with different types
+
+use crate::Vechonk;
+use core::any::Any;
+use core::mem::ManuallyDrop;
+use core::ptr;
+
+macro_rules! impl_any {
+    ($dyn_any:ty, $($bounds:tt)+) => {
+        impl Vechonk<$dyn_any> {
+            /// Get a reference to the element at the index if it's a `U`. Returns `None` if the index is out of
+            /// bounds or the element has a different type, O(1)
+            pub fn get_as<U: Any>(&self, index: usize) -> Option<&U> {
+                self.get(index)?.downcast_ref()
+            }
+
+            /// Get a mutable reference to the element at the index if it's a `U`. Returns `None` if the index is
+            /// out of bounds or the element has a different type, O(1)
+            pub fn get_as_mut<U: Any>(&mut self, index: usize) -> Option<&mut U> {
+                self.get_mut(index)?.downcast_mut()
+            }
+
+            /// An iterator over the elements that are a `U`, skipping all others
+            pub fn iter_of<U: Any>(&self) -> impl Iterator<Item = &U> + '_ {
+                self.iter().filter_map(|elem| elem.downcast_ref())
+            }
+
+            /// Pushes a value without putting it into a `Box` first
+            pub fn push_value<U: $($bounds)+>(&mut self, value: U) {
+                let value = ManuallyDrop::new(value);
+                let elem: &$dyn_any = &*value;
+
+                // SAFETY: `value` lives on the stack, so it's not inside of the `Vechonk`.
+                //         It's wrapped in `ManuallyDrop`, so it's not used or dropped after being moved
+                unsafe { self.raw.push_raw(elem) };
+                self.debug_check_invariants();
+            }
+
+            /// Removes the last element and returns it if it's a `U`. If the `Vechonk` is empty or the last
+            /// element has a different type, it stays in place and `None` is returned
+            pub fn downcast_pop<U: Any>(&mut self) -> Option<U> {
+                let last = self.len().checked_sub(1)?;
+
+                if !self[last].is::<U>() {
+                    return None;
+                }
+
+                // SAFETY: The index is in bounds, and we just checked that the element is a `U`.
+                //         It's removed right after being moved out, so it can't be used again
+                unsafe {
+                    let value = ptr::read(self.raw.get_unchecked_ptr(last).cast::<U>());
+                    self.raw.remove_last();
+                    self.debug_check_invariants();
+                    Some(value)
+                }
+            }
+        }
+    };
+}
+
+impl_any!(dyn Any, Any);
+impl_any!(dyn Any + Send, Any + Send);
+impl_any!(dyn Any + Send + Sync, Any + Send + Sync);
@@ -0,0 +1,212 @@
+use crate::force_align;
+use crate::raw::{dealloc_box, PtrData, RawVechonk};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::{Cell, UnsafeCell};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Index;
+
+/// The capacity of the first chunk, if none is given
+const MIN_CHUNK_CAPACITY: usize = 64;
+
+/// An append-only [`Vechonk`](crate::Vechonk), that can be pushed to through a shared reference
+///
+/// Instead of regrowing and moving all elements like a `Vechonk`, it allocates a new, bigger chunk when
+/// the current one is full. Elements therefore never move, and references to them stay valid while pushing,
+/// which makes it usable as an arena or as the backing storage of an interner.
+///
+/// Indexing is O(log chunks), since the chunk has to be found first.
+///
+/// note: it does not run destructors for now, just like `Vechonk`
+pub struct AppendOnlyVechonk<T: ?Sized> {
+    /// The chunks together with the index of their first element. Elements are only pushed to the last chunk
+    chunks: UnsafeCell<Vec<(usize, RawVechonk<T>)>>,
+    len: Cell<usize>,
+    _marker: PhantomData<Box<T>>,
+}
+
+impl<T: ?Sized> AppendOnlyVechonk<T> {
+    /// Create a new empty `AppendOnlyVechonk` that doesn't allocate anything
+    pub const fn new() -> Self {
+        Self {
+            chunks: UnsafeCell::new(Vec::new()),
+            len: Cell::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new `AppendOnlyVechonk` with a first chunk of `capacity` bytes. `capacity` gets shrunken down
+    /// to the next multiple of the alignment of usize + metadata of `T`
+    pub fn with_capacity(capacity: usize) -> Self {
+        let chonk = Self::new();
+        chonk
+            .chunks_mut()
+            .push((0, RawVechonk::with_capacity(capacity)));
+        chonk
+    }
+
+    /// The amount of elements in the `AppendOnlyVechonk`, O(1)
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Whether the `AppendOnlyVechonk` is empty, O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes a new element and returns a reference to it. Existing elements are never moved,
+    /// if there's not enough space, a new chunk is allocated instead.
+    pub fn push(&self, element: Box<T>) -> &T {
+        let chunks = self.chunks_mut();
+
+        let needs_chunk = match chunks.last() {
+            Some((_, chunk)) => !chunk.has_space_for(&element),
+            None => true,
+        };
+
+        if needs_chunk {
+            let last_cap = chunks.last().map_or(0, |(_, chunk)| chunk.cap);
+
+            // enough space for the element, even if it needs the worst case padding
+            let required = mem::size_of_val::<T>(&element)
+                + mem::align_of_val::<T>(&element)
+                + mem::size_of::<PtrData<T>>() * 2;
+
+            let capacity = force_align(
+                (last_cap * 2).max(required).max(MIN_CHUNK_CAPACITY),
+                mem::align_of::<PtrData<T>>(),
+            );
+
+            chunks.push((self.len(), RawVechonk::with_capacity(capacity)));
+        }
+
+        // there's always a last chunk, since we have pushed one if there wasn't
+        let (_, chunk) = chunks.last_mut().unwrap();
+
+        let elem_ptr = Box::into_raw(element);
+
+        // SAFETY: The element lives in the `Box`, so it's not inside of the chunk. It's moved out if it was pushed,
+        //         and the chunk must never regrow, so a mistake in the capacity above panics instead of pushing it
+        let pushed = unsafe { chunk.try_push_raw(elem_ptr) };
+        assert!(pushed, "the new element doesn't fit into the chunk");
+
+        // SAFETY: The pointer comes from the `Box`, and the value has been moved out above
+        unsafe { dealloc_box(elem_ptr) };
+
+        self.len.set(self.len() + 1);
+
+        // SAFETY: We just pushed the element, so it's in bounds. The chunk never regrows, so the element
+        //         will stay at this address until the `AppendOnlyVechonk` is dropped
+        unsafe { &*chunk.get_unchecked_ptr(chunk.len - 1) }
+    }
+
+    /// Get a reference to an element at the index. Returns `None` if the index is out of bounds, O(log chunks)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let chunks = self.chunks();
+
+        // the last chunk that starts at or before `index`, there must be one since the first one starts at 0
+        let chunk_index = chunks.partition_point(|(start, _)| *start <= index) - 1;
+        let (start, chunk) = &chunks[chunk_index];
+
+        // SAFETY: `index` is in bounds, so it must be in the chunk that starts right before it.
+        //         Elements never move, see `Self::push`
+        unsafe { Some(&*chunk.get_unchecked_ptr(index - start)) }
+    }
+
+    /// An iterator over the elements yielding shared references. Elements that are pushed
+    /// while iterating are yielded as well
+    pub fn iter(&self) -> AppendOnlyIter<'_, T> {
+        AppendOnlyIter {
+            chonk: self,
+            chunk_index: 0,
+            index_in_chunk: 0,
+        }
+    }
+
+    /// The amount of chunks that have been allocated
+    pub fn chunk_count(&self) -> usize {
+        self.chunks().len()
+    }
+
+    fn chunks(&self) -> &Vec<(usize, RawVechonk<T>)> {
+        // SAFETY: The chunks are only mutated in `Self::push`, which never runs at the same time,
+        //         since `AppendOnlyVechonk` is not `Sync` and doesn't call any user code while pushing.
+        //         References to the elements don't point into the `Vec`, so pushing to it doesn't invalidate them
+        unsafe { &*self.chunks.get() }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn chunks_mut(&self) -> &mut Vec<(usize, RawVechonk<T>)> {
+        // SAFETY: See `Self::chunks`. The returned reference is never held across calls into user code
+        unsafe { &mut *self.chunks.get() }
+    }
+}
+
+impl<T: ?Sized> Default for AppendOnlyVechonk<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> Index<usize> for AppendOnlyVechonk<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(elem) => elem,
+            None => panic!("Out of bounds, index {} for len {}", index, self.len()),
+        }
+    }
+}
+
+/// don't bother with destructors for now
+impl<T: ?Sized> Drop for AppendOnlyVechonk<T> {
+    fn drop(&mut self) {
+        for (_, chunk) in self.chunks.get_mut() {
+            // SAFETY: We own the chunks, and they have the length `chunk.cap`
+            unsafe {
+                RawVechonk::<T>::dealloc(chunk.cap, chunk.ptr.as_ptr());
+            }
+        }
+    }
+}
+
+/// An iterator over the elements of an [`AppendOnlyVechonk`]
+pub struct AppendOnlyIter<'a, T: ?Sized> {
+    chonk: &'a AppendOnlyVechonk<T>,
+    chunk_index: usize,
+    index_in_chunk: usize,
+}
+
+impl<'a, T: ?Sized> Iterator for AppendOnlyIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunks = self.chonk.chunks();
+
+        loop {
+            let (_, chunk) = chunks.get(self.chunk_index)?;
+
+            if self.index_in_chunk < chunk.len {
+                // SAFETY: We just did a bounds check, and elements never move, see `AppendOnlyVechonk::push`
+                let elem = unsafe { &*chunk.get_unchecked_ptr(self.index_in_chunk) };
+                self.index_in_chunk += 1;
+                return Some(elem);
+            }
+
+            // only continue with the next chunk if there is one, new elements might still be pushed into this one
+            if self.chunk_index + 1 == chunks.len() {
+                return None;
+            }
+
+            self.chunk_index += 1;
+            self.index_in_chunk = 0;
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! A `Vechonk` in a buffer provided by the caller, which never allocates and works without `alloc`
+
+use crate::access::{impl_access, RawAccess};
+use crate::force_align;
+use crate::raw::{PtrData, RawVechonk};
+use core::cmp;
+use core::marker::{PhantomData, Unsize};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ptr::NonNull;
+#[cfg(feature = "alloc")]
+use {crate::raw::dealloc_box, alloc::boxed::Box};
+
+/// A [`Vechonk`](crate::Vechonk) with the same layout, that lives in a fixed buffer borrowed from the caller
+///
+/// It never allocates, so it is available without the `alloc` feature. Pushing returns the element back
+/// instead of regrowing when the buffer is full.
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// # use vechonk::VechonkBuf;
+/// let mut buf = [MaybeUninit::uninit(); 64];
+/// let mut chonk = VechonkBuf::<str>::new(&mut buf);
+///
+/// chonk.try_push_str("hello").unwrap();
+/// chonk.try_push_str("uwu").unwrap();
+///
+/// assert_eq!(&chonk[1], "uwu");
+/// assert!(chonk.try_push_str(&"a".repeat(64)).is_err());
+/// ```
+///
+/// note: it does not run destructors for now, just like `Vechonk`
+pub struct VechonkBuf<'a, T: ?Sized> {
+    raw: RawVechonk<T>,
+    _buf: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+// SAFETY: The `VechonkBuf` owns its elements and exclusively borrows the buffer, just like a `Vechonk`
+unsafe impl<T: ?Sized + Send> Send for VechonkBuf<'_, T> {}
+// SAFETY: Shared references only hand out shared references to the elements
+unsafe impl<T: ?Sized + Sync> Sync for VechonkBuf<'_, T> {}
+
+impl<'a, T: ?Sized> VechonkBuf<'a, T> {
+    /// Create a new empty `VechonkBuf` in `buf`. The start of the buffer is skipped until it's aligned
+    /// for the metadata, and the rest is shrunken down to a multiple of that alignment
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        let align = mem::align_of::<PtrData<T>>();
+        let ptr = buf.as_mut_ptr().cast::<u8>();
+
+        // the metadata is laid out from `ptr + cap`, so both have to be aligned for it
+        let start = cmp::min(ptr.align_offset(align), buf.len());
+
+        let mut raw = RawVechonk::new();
+        // SAFETY: `start` is at most the length of the buffer, and the pointer of a slice is never null
+        raw.ptr = unsafe { NonNull::new_unchecked(ptr.add(start)) };
+        raw.cap = force_align(buf.len() - start, align);
+
+        Self {
+            raw,
+            _buf: PhantomData,
+        }
+    }
+
+    /// The amount of elements in the `VechonkBuf`, O(1)
+    pub const fn len(&self) -> usize {
+        self.raw.len
+    }
+
+    /// The amount of bytes of the buffer that are used for elements and metadata, after it has been aligned
+    pub const fn capacity(&self) -> usize {
+        self.raw.cap
+    }
+
+    /// The amount of bytes between the elements and the metadata, that can be used by new elements
+    pub const fn bytes_free(&self) -> usize {
+        self.raw.cap - self.raw.elem_size - self.raw.data_section_size()
+    }
+
+    /// Pushes a value without putting it into a `Box` first, unsizing it to `T`. Returns it back if it doesn't fit
+    pub fn try_push_value<U: Unsize<T>>(&mut self, value: U) -> Result<(), U> {
+        let value = ManuallyDrop::new(value);
+        let elem: &T = &*value;
+
+        // SAFETY: `value` lives on the stack, so it's not inside of the `VechonkBuf`.
+        //         It's wrapped in `ManuallyDrop`, so it's not used or dropped after being moved
+        if unsafe { self.raw.try_push_raw(elem) } {
+            Ok(())
+        } else {
+            Err(ManuallyDrop::into_inner(value))
+        }
+    }
+
+    /// Passes the last element to `f` and removes it afterwards, without running its destructor.
+    /// Returns what `f` returned, or `None` if the `VechonkBuf` is empty. Doesn't need `alloc`, unlike `pop`
+    pub fn pop_with<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // SAFETY: The `VechonkBuf` isn't empty, so the last index is in bounds
+        let last = unsafe { &mut *self.raw.get_unchecked_ptr(self.len() - 1) };
+
+        let result = f(last);
+
+        // SAFETY: The `VechonkBuf` isn't empty, and the element is never touched again
+        unsafe { self.raw.remove_last() };
+
+        Some(result)
+    }
+
+    /// Removes the elements from `len` onwards without running their destructors. Does nothing if there
+    /// aren't more than `len` elements
+    pub fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            // SAFETY: The `VechonkBuf` isn't empty, and the element is never touched again
+            unsafe { self.raw.remove_last() };
+        }
+    }
+
+    /// Removes all elements without running their destructors, so that the whole buffer can be used again
+    pub fn clear(&mut self) {
+        self.raw.len = 0;
+        self.raw.elem_size = 0;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> VechonkBuf<'_, T> {
+    /// Moves the element out of the `Box` to the end. Returns the `Box` back if it doesn't fit
+    pub fn try_push(&mut self, element: Box<T>) -> Result<(), Box<T>> {
+        // SAFETY: The element lives in the `Box`, so it's not inside of the `VechonkBuf`.
+        //         If it was pushed, it has been moved out, and only the allocation of the `Box` is freed below
+        if !unsafe { self.raw.try_push_raw(&*element) } {
+            return Err(element);
+        }
+
+        // SAFETY: The pointer comes from the `Box`, and the value has been moved out above
+        unsafe { dealloc_box(Box::into_raw(element)) };
+        Ok(())
+    }
+
+    /// Removes the last element and returns it in a `Box`, or `None` if the `VechonkBuf` is empty
+    pub fn pop(&mut self) -> Option<Box<T>> {
+        self.raw.pop()
+    }
+}
+
+impl VechonkBuf<'_, str> {
+    /// Copies `element` to the end. Returns it back if it doesn't fit
+    pub fn try_push_str<'s>(&mut self, element: &'s str) -> Result<(), &'s str> {
+        // SAFETY: `str` doesn't own anything, so copying its bytes is fine
+        if unsafe { self.raw.try_push_raw(element) } {
+            Ok(())
+        } else {
+            Err(element)
+        }
+    }
+}
+
+impl<T: Copy> VechonkBuf<'_, [T]> {
+    /// Copies `element` to the end. Returns it back if it doesn't fit
+    pub fn try_push_slice<'s>(&mut self, element: &'s [T]) -> Result<(), &'s [T]> {
+        // SAFETY: `T` is `Copy`, so copying the bytes is fine
+        if unsafe { self.raw.try_push_raw(element) } {
+            Ok(())
+        } else {
+            Err(element)
+        }
+    }
+}
+
+// SAFETY: `raw` views the elements in the borrowed buffer, which lives for `'a`
+unsafe impl<'a, T: ?Sized> RawAccess<T> for VechonkBuf<'a, T> {
+    fn raw_view(&self) -> RawVechonk<T> {
+        self.raw.copy()
+    }
+
+    fn raw_view_mut(&mut self) -> RawVechonk<T> {
+        self.raw.copy()
+    }
+}
+
+impl_access!(['a, T: ?Sized] VechonkBuf<'a, T>, "VechonkBuf");
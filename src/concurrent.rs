@@ -0,0 +1,444 @@
+use crate::force_align;
+use crate::raw::{dealloc_box, PtrData, RawVechonk};
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use core::{cmp, mem};
+
+/// The state of a chunk is packed into a single `usize`, so that element bytes and `PtrData` slots can be
+/// reserved together with a single compare exchange:
+/// * the highest bit is set once the chunk is full, so that no more elements are pushed into it
+/// * the next `SLOT_BITS` bits are the amount of reserved `PtrData` slots, growing from the back
+/// * the rest are the amount of reserved element bytes, growing from the front
+const SLOT_BITS: u32 = usize::BITS / 4;
+const BYTE_BITS: u32 = usize::BITS - SLOT_BITS - 1;
+const CLOSED: usize = 1 << (usize::BITS - 1);
+const BYTE_MASK: usize = (1 << BYTE_BITS) - 1;
+const MAX_SLOTS: usize = (1 << SLOT_BITS) - 1;
+const READY_BITS: usize = usize::BITS as usize;
+
+/// The capacity of the first chunk
+const MIN_CHUNK_CAPACITY: usize = 256;
+/// The amount of reserved bytes has to fit into the state, so a chunk can't be bigger than this
+const MAX_CHUNK_CAPACITY: usize = BYTE_MASK;
+
+const fn pack(bytes: usize, slots: usize) -> usize {
+    bytes | (slots << BYTE_BITS)
+}
+
+const fn reserved_bytes(state: usize) -> usize {
+    state & BYTE_MASK
+}
+
+const fn reserved_slots(state: usize) -> usize {
+    (state >> BYTE_BITS) & MAX_SLOTS
+}
+
+/// A single allocation with the layout of a [`Vechonk`](crate::Vechonk), that is filled from multiple threads
+struct Chunk<T: ?Sized> {
+    /// Only `ptr` and `cap` are used, the rest is tracked by the atomics
+    raw: RawVechonk<T>,
+    state: AtomicUsize,
+    /// Elements are published in the order of their slots, all slots below this are initialized
+    committed: AtomicUsize,
+    /// A bit for every slot, that is set once its element has been written but might not be published yet
+    ready: Box<[AtomicUsize]>,
+    next: AtomicPtr<Chunk<T>>,
+}
+
+impl<T: ?Sized> Chunk<T> {
+    fn new(capacity: usize) -> Box<Self> {
+        let raw = RawVechonk::with_capacity(capacity);
+
+        let max_slots = cmp::min(raw.cap / mem::size_of::<PtrData<T>>(), MAX_SLOTS);
+        let ready = (0..(max_slots + READY_BITS - 1) / READY_BITS)
+            .map(|_| AtomicUsize::new(0))
+            .collect();
+
+        Box::new(Self {
+            raw,
+            state: AtomicUsize::new(0),
+            committed: AtomicUsize::new(0),
+            ready,
+            next: AtomicPtr::new(ptr::null_mut()),
+        })
+    }
+
+    /// Reserves space for an element and a `PtrData` slot. Returns the offset of the element and the slot,
+    /// or `None` if the chunk is full. The chunk is closed if the element doesn't fit.
+    fn reserve(&self, elem_size: usize, elem_align: usize) -> Option<(usize, usize)> {
+        let mut state = self.state.load(Ordering::Relaxed);
+
+        loop {
+            if state & CLOSED != 0 {
+                return None;
+            }
+
+            let (bytes, slots) = (reserved_bytes(state), reserved_slots(state));
+
+            let required_align_offset = self
+                .raw
+                .ptr
+                .as_ptr()
+                .wrapping_add(bytes)
+                .align_offset(elem_align);
+
+            if required_align_offset == usize::MAX {
+                panic!(
+                    "Cannot align pointer for element with size: {}, alignment: {}",
+                    elem_size, elem_align
+                );
+            }
+
+            let offset = bytes + required_align_offset;
+            let end = offset + elem_size;
+            let data_size = mem::size_of::<PtrData<T>>() * (slots + 1);
+
+            let new_state = if slots < MAX_SLOTS && end + data_size <= self.raw.cap {
+                pack(end, slots + 1)
+            } else {
+                state | CLOSED
+            };
+
+            match self.state.compare_exchange_weak(
+                state,
+                new_state,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) if new_state & CLOSED != 0 => return None,
+                Ok(_) => return Some((offset, slots)),
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    /// Marks the element in `slot` as written, and publishes it together with all written elements directly
+    /// behind the published ones. Whoever marks their slot last publishes the others as well, so this never waits.
+    fn publish(&self, slot: usize) {
+        // Everything is `SeqCst`, so that for two pushes, at least one of them sees that the other one is ready
+        self.ready[slot / READY_BITS].fetch_or(1 << (slot % READY_BITS), Ordering::SeqCst);
+
+        let mut committed = self.committed.load(Ordering::SeqCst);
+
+        while self.is_ready(committed) {
+            match self.committed.compare_exchange(
+                committed,
+                committed + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => committed += 1,
+                Err(current) => committed = current,
+            }
+        }
+    }
+
+    fn is_ready(&self, slot: usize) -> bool {
+        self.ready.get(slot / READY_BITS).map_or(false, |word| {
+            word.load(Ordering::SeqCst) & (1 << (slot % READY_BITS)) != 0
+        })
+    }
+
+    /// Whether no more elements will be published in this chunk, so that the next one can be read
+    fn is_complete(&self, committed: usize) -> bool {
+        let state = self.state.load(Ordering::Acquire);
+        state & CLOSED != 0 && committed == reserved_slots(state)
+    }
+}
+
+impl<T: ?Sized> Drop for Chunk<T> {
+    fn drop(&mut self) {
+        // SAFETY: We own the allocation, and it has the length `self.raw.cap`
+        unsafe {
+            RawVechonk::<T>::dealloc(self.raw.cap, self.raw.ptr.as_ptr());
+        }
+    }
+}
+
+/// An append-only [`Vechonk`](crate::Vechonk) that can be pushed to from multiple threads at the same time
+///
+/// Element bytes are reserved from the front of a chunk and `PtrData` slots from the back, both with a single
+/// atomic compare exchange. When a chunk is full, a new, bigger one is allocated, so elements never move and
+/// references to them stay valid. Reading never blocks, but only sees elements once all elements that were
+/// reserved before them in the same chunk have been written. Pushes never wait for each other, the push that
+/// finishes writing last publishes the ones before it.
+///
+/// Elements are ordered by their chunk first, and then by the order in which their space was reserved.
+///
+/// note: it does not run destructors for now, just like `Vechonk`
+pub struct ConcurrentVechonk<T: ?Sized> {
+    /// The first chunk, or null
+    head: AtomicPtr<Chunk<T>>,
+    /// The chunk that elements are currently pushed to, or null. This is only a hint, as it might lag behind
+    tail: AtomicPtr<Chunk<T>>,
+    _marker: PhantomData<Box<T>>,
+}
+
+// SAFETY: The `ConcurrentVechonk` owns its elements, so it can be sent if they can be sent
+unsafe impl<T: ?Sized + Send> Send for ConcurrentVechonk<T> {}
+// SAFETY: Elements can be pushed from another thread, so they must be `Send`, and references to them can be
+//         obtained from multiple threads, so they must be `Sync`. The chunks themselves are synchronized with atomics
+unsafe impl<T: ?Sized + Send + Sync> Sync for ConcurrentVechonk<T> {}
+
+impl<T: ?Sized> ConcurrentVechonk<T> {
+    /// Create a new empty `ConcurrentVechonk` that doesn't allocate anything
+    pub const fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            tail: AtomicPtr::new(ptr::null_mut()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pushes a new element and returns a reference to it. This can be called from multiple threads at once.
+    ///
+    /// The element only becomes visible to [`ConcurrentVechonk::get`] and friends once all pushes that reserved
+    /// their space before it in the same chunk have finished as well
+    ///
+    /// Panics if the element is too big to fit into a single chunk
+    pub fn push(&self, element: Box<T>) -> &T {
+        let elem_size = mem::size_of_val::<T>(&element);
+        let elem_align = mem::align_of_val::<T>(&element);
+
+        let mut chunk = self.tail_chunk(elem_size, elem_align);
+
+        let (offset, slot) = loop {
+            match chunk.reserve(elem_size, elem_align) {
+                Some(reserved) => break reserved,
+                None => chunk = self.next_chunk(chunk, elem_size, elem_align),
+            }
+        };
+
+        let elem_ptr = Box::into_raw(element);
+
+        // SAFETY: We have reserved the element bytes and the slot above, so no one else writes to them.
+        //         `elem_ptr` comes from `Box` and is valid to read from for the size, the allocations can't overlap
+        unsafe {
+            let dest_ptr = chunk.raw.ptr.as_ptr().add(offset);
+            ptr::copy_nonoverlapping::<u8>(elem_ptr as _, dest_ptr, elem_size);
+            chunk.raw.write_meta_data(elem_ptr, offset, slot);
+        }
+
+        chunk.publish(slot);
+
+        // SAFETY: `elem_ptr` comes from a `Box<T>`, and the value has been moved out of it
+        unsafe { dealloc_box(elem_ptr) };
+
+        // SAFETY: We have written the element above, and it never moves. It might not be published yet,
+        //         but only other threads care about that
+        unsafe { &*chunk.raw.get_unchecked_ptr(slot) }
+    }
+
+    /// Get a reference to an element at the index. Returns `None` if the index is out of bounds or the
+    /// element hasn't been published yet. O(chunks)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let mut index = index;
+        let mut chunk = self.head.load(Ordering::Acquire);
+
+        // SAFETY: Chunks are never freed while `self` is borrowed
+        while let Some(current) = unsafe { chunk.as_ref() } {
+            let committed = current.committed.load(Ordering::Acquire);
+
+            if index < committed {
+                // SAFETY: All slots below `committed` have been published
+                return unsafe { Some(&*current.raw.get_unchecked_ptr(index)) };
+            }
+
+            if !current.is_complete(committed) {
+                return None;
+            }
+
+            index -= committed;
+            chunk = current.next.load(Ordering::Acquire);
+        }
+
+        None
+    }
+
+    /// The amount of elements that have been published, O(chunks)
+    pub fn len(&self) -> usize {
+        let mut len = 0;
+        let mut chunk = self.head.load(Ordering::Acquire);
+
+        // SAFETY: Chunks are never freed while `self` is borrowed
+        while let Some(current) = unsafe { chunk.as_ref() } {
+            let committed = current.committed.load(Ordering::Acquire);
+            len += committed;
+
+            if !current.is_complete(committed) {
+                break;
+            }
+
+            chunk = current.next.load(Ordering::Acquire);
+        }
+
+        len
+    }
+
+    /// Whether no elements have been published yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An iterator over the published elements yielding shared references. Elements that are published
+    /// while iterating are yielded as well
+    pub fn iter(&self) -> ConcurrentIter<'_, T> {
+        ConcurrentIter {
+            chunk: self.head.load(Ordering::Acquire),
+            index_in_chunk: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The chunk that elements should be pushed to, allocating the first one if needed
+    fn tail_chunk(&self, elem_size: usize, elem_align: usize) -> &Chunk<T> {
+        let tail = self.tail.load(Ordering::Acquire);
+        // SAFETY: Chunks are never freed while `self` is borrowed
+        if let Some(tail) = unsafe { tail.as_ref() } {
+            return tail;
+        }
+
+        // the tail might not have been set yet, but the head is enough, we will follow the chain from there
+        let head = self.head.load(Ordering::Acquire);
+        // SAFETY: Chunks are never freed while `self` is borrowed
+        if let Some(head) = unsafe { head.as_ref() } {
+            return head;
+        }
+
+        let new = Box::into_raw(Chunk::new(chunk_capacity::<T>(0, elem_size, elem_align)));
+
+        match self
+            .head
+            .compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => {
+                let _ = self.tail.compare_exchange(
+                    ptr::null_mut(),
+                    new,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                );
+                // SAFETY: We just created the chunk, and it's never freed while `self` is borrowed
+                unsafe { &*new }
+            }
+            Err(head) => {
+                // SAFETY: Someone else was faster, so our chunk was never shared
+                drop(unsafe { Box::from_raw(new) });
+                // SAFETY: Chunks are never freed while `self` is borrowed
+                unsafe { &*head }
+            }
+        }
+    }
+
+    /// The chunk after `chunk`, allocating it if it doesn't exist yet
+    fn next_chunk(&self, chunk: &Chunk<T>, elem_size: usize, elem_align: usize) -> &Chunk<T> {
+        let chunk_ptr = chunk as *const Chunk<T> as *mut Chunk<T>;
+        let mut next = chunk.next.load(Ordering::Acquire);
+
+        if next.is_null() {
+            let new = Box::into_raw(Chunk::new(chunk_capacity::<T>(
+                chunk.raw.cap,
+                elem_size,
+                elem_align,
+            )));
+
+            match chunk.next.compare_exchange(
+                ptr::null_mut(),
+                new,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => next = new,
+                Err(existing) => {
+                    // SAFETY: Someone else was faster, so our chunk was never shared
+                    drop(unsafe { Box::from_raw(new) });
+                    next = existing;
+                }
+            }
+        }
+
+        // it doesn't matter if this fails, then someone else has already moved the tail further
+        let _ = self
+            .tail
+            .compare_exchange(chunk_ptr, next, Ordering::AcqRel, Ordering::Acquire);
+
+        // SAFETY: Chunks are never freed while `self` is borrowed
+        unsafe { &*next }
+    }
+}
+
+impl<T: ?Sized> Default for ConcurrentVechonk<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// don't bother with destructors for now
+impl<T: ?Sized> Drop for ConcurrentVechonk<T> {
+    fn drop(&mut self) {
+        let mut chunk = *self.head.get_mut();
+
+        while !chunk.is_null() {
+            // SAFETY: We have exclusive access, and all chunks were created by `Box`
+            let mut current = unsafe { Box::from_raw(chunk) };
+            chunk = *current.next.get_mut();
+        }
+    }
+}
+
+/// The capacity of a new chunk, that is at least twice as big as the last one and fits the element
+fn chunk_capacity<T: ?Sized>(last_capacity: usize, elem_size: usize, elem_align: usize) -> usize {
+    // enough space for the element, even if it needs the worst case padding
+    let required = elem_size + elem_align + mem::size_of::<PtrData<T>>() * 2;
+
+    if required > MAX_CHUNK_CAPACITY {
+        panic!(
+            "Element with size: {}, alignment: {} is too big for a chunk",
+            elem_size, elem_align
+        );
+    }
+
+    let capacity = (last_capacity * 2)
+        .max(required)
+        .max(MIN_CHUNK_CAPACITY)
+        .min(MAX_CHUNK_CAPACITY);
+
+    force_align(capacity, mem::align_of::<PtrData<T>>())
+}
+
+/// An iterator over the elements of a [`ConcurrentVechonk`]
+pub struct ConcurrentIter<'a, T: ?Sized> {
+    chunk: *const Chunk<T>,
+    index_in_chunk: usize,
+    _marker: PhantomData<&'a ConcurrentVechonk<T>>,
+}
+
+impl<'a, T: ?Sized> Iterator for ConcurrentIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // SAFETY: Chunks are never freed while the `ConcurrentVechonk` is borrowed
+            let chunk = unsafe { self.chunk.as_ref()? };
+            let committed = chunk.committed.load(Ordering::Acquire);
+
+            if self.index_in_chunk < committed {
+                // SAFETY: All slots below `committed` have been published, and elements never move
+                let elem = unsafe { &*chunk.raw.get_unchecked_ptr(self.index_in_chunk) };
+                self.index_in_chunk += 1;
+                return Some(elem);
+            }
+
+            // only continue with the next chunk if nothing will be published in this one anymore
+            let next = chunk.next.load(Ordering::Acquire);
+            if !chunk.is_complete(committed) || next.is_null() {
+                return None;
+            }
+
+            self.chunk = next;
+            self.index_in_chunk = 0;
+        }
+    }
+}
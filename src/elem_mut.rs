@@ -0,0 +1,160 @@
+use crate::Vechonk;
+use core::ops::{Deref, DerefMut};
+use core::{mem, ptr};
+
+/// A mutable guard to an element of a [`Vechonk`] that can change the size of the element,
+/// created by [`Vechonk::get_resizable`]
+///
+/// If there is enough space behind the element, it grows in place. Otherwise, it is moved to the end of
+/// the `Vechonk`, which might regrow it. The metadata is updated right away, so forgetting the guard is fine.
+///
+/// Growing the element at the end of the buffer, like the one that was pushed last, is O(1) unless it regrows.
+/// Other elements have to find the free space around them first, like [`Vechonk::try_replace`].
+pub struct ElemMut<'a, T: ?Sized> {
+    chonk: &'a mut Vechonk<T>,
+    index: usize,
+}
+
+impl<'a, T: ?Sized> ElemMut<'a, T> {
+    /// # Safety
+    /// The index must be in bounds
+    pub(crate) unsafe fn new(chonk: &'a mut Vechonk<T>, index: usize) -> Self {
+        Self { chonk, index }
+    }
+}
+
+impl<T: ?Sized> Deref for ElemMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The index has been checked when creating the guard, and the `Vechonk` can't change in the meantime
+        unsafe { self.chonk.get_unchecked(self.index) }
+    }
+}
+
+impl<T: ?Sized> DerefMut for ElemMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The index has been checked when creating the guard, and the `Vechonk` can't change in the meantime
+        unsafe { self.chonk.get_unchecked_mut(self.index) }
+    }
+}
+
+impl ElemMut<'_, str> {
+    /// Appends a string to the end of the element
+    pub fn push_str(&mut self, string: &str) {
+        let len = self.len();
+        let new_len = len + string.len();
+
+        // SAFETY: The index is in bounds
+        let offset = unsafe { self.chonk.raw.reserve_elem(self.index, new_len) };
+
+        // SAFETY: We have reserved enough space for the new bytes above, and `string` can't be inside of the
+        //         `Vechonk`, since we borrow it mutably. The new metadata covers exactly the written bytes,
+        //         and appending valid UTF-8 to valid UTF-8 keeps it valid
+        unsafe {
+            let dest = self.chonk.raw.ptr.as_ptr().add(offset + len);
+            ptr::copy_nonoverlapping(string.as_ptr(), dest, string.len());
+            self.chonk.raw.set_meta(self.index, new_len);
+        }
+
+        self.chonk.debug_check_invariants();
+    }
+
+    /// Appends a char to the end of the element
+    pub fn push(&mut self, ch: char) {
+        self.push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Shortens the element to `new_len` bytes. Does nothing if `new_len` is greater than the length.
+    /// The memory of the removed part is not reclaimed until the `Vechonk` is compacted.
+    ///
+    /// Panics if `new_len` does not lie on a char boundary, like [`alloc::string::String::truncate`]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(
+                self.is_char_boundary(new_len),
+                "new_len is not a char boundary"
+            );
+
+            // SAFETY: The index is in bounds, and the element is still valid UTF-8, since we cut it at a char boundary
+            unsafe { self.chonk.raw.set_meta(self.index, new_len) };
+            self.chonk.debug_check_invariants();
+        }
+    }
+}
+
+impl<T> ElemMut<'_, [T]> {
+    /// Appends an item to the end of the element
+    pub fn push(&mut self, value: T) {
+        let len = self.len();
+
+        // SAFETY: The index is in bounds
+        let offset = unsafe {
+            self.chonk
+                .raw
+                .reserve_elem(self.index, mem::size_of::<T>() * (len + 1))
+        };
+
+        // SAFETY: We have reserved enough space for the new item above, and the offset is aligned for `T`,
+        //         since the element is aligned. The new metadata includes the newly initialized item
+        unsafe {
+            let items = self.chonk.raw.ptr.as_ptr().add(offset).cast::<T>();
+            ptr::write(items.add(len), value);
+            self.chonk.raw.set_meta(self.index, len + 1);
+        }
+
+        self.chonk.debug_check_invariants();
+    }
+
+    /// Shortens the element to `new_len` items, dropping the rest. Does nothing if `new_len` is greater than
+    /// the length. The memory of the removed items is not reclaimed until the `Vechonk` is compacted.
+    pub fn truncate(&mut self, new_len: usize) {
+        let len = self.len();
+
+        if new_len >= len {
+            return;
+        }
+
+        // SAFETY: The index is in bounds, and the first `new_len` items stay initialized
+        unsafe { self.chonk.raw.set_meta(self.index, new_len) };
+
+        // SAFETY: The items are not part of the element anymore, so they can't be used again
+        unsafe {
+            let items = self.chonk.raw.get_unchecked_ptr(self.index).cast::<T>();
+            let tail = ptr::slice_from_raw_parts_mut(items.add(new_len), len - new_len);
+            ptr::drop_in_place(tail);
+        }
+
+        self.chonk.debug_check_invariants();
+    }
+}
+
+impl<T: Clone> ElemMut<'_, [T]> {
+    /// Clones and appends all items of `other` to the end of the element
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        let len = self.len();
+
+        // reserve the space once, so that the element is moved at most once
+        // SAFETY: The index is in bounds
+        let offset = unsafe {
+            self.chonk
+                .raw
+                .reserve_elem(self.index, mem::size_of::<T>() * (len + other.len()))
+        };
+
+        for (i, item) in other.iter().enumerate() {
+            let item = item.clone();
+
+            // SAFETY: We have reserved enough space for all new items above, and the offset is aligned for `T`.
+            //         The metadata is updated after every item, so only initialized items are part of the element,
+            //         even if `clone` panics
+            unsafe {
+                let items = self.chonk.raw.ptr.as_ptr().add(offset).cast::<T>();
+                ptr::write(items.add(len + i), item);
+                self.chonk.raw.set_meta(self.index, len + i + 1);
+            }
+        }
+
+        self.chonk.debug_check_invariants();
+    }
+}
@@ -0,0 +1,314 @@
+//! A C API for `Vechonk<str>` and `Vechonk<[u8]>`, behind the `ffi` feature
+//!
+//! The header `include/vechonk.h` is generated from this module with `cbindgen`, see `cbindgen.toml`.
+//!
+//! All functions taking a pointer to a `VechonkStr` or `VechonkBytes` expect it to come from the matching
+//! `*_new` function and not to have been freed yet. Element pointers handed out to C are valid until the
+//! `Vechonk` is pushed to or freed.
+//!
+//! Unwinding out of an `extern "C"` function is undefined behaviour, so every function catches panics at the
+//! boundary and returns a failure value instead: `false`, null or `0`. The `*_iter` functions only fill in a
+//! struct, so they can't panic.
+//! Panics only come from bugs or from the capacity overflowing, and with the `debug-invariants` feature from
+//! broken invariants. Catching them needs `std`, so the `ffi` feature links it.
+
+extern crate std;
+
+use crate::Vechonk;
+use alloc::boxed::Box;
+use core::{ptr, slice, str};
+use std::panic::{self, AssertUnwindSafe};
+
+/// A `Vechonk<str>`, containing UTF-8 strings
+pub struct VechonkStr {
+    chonk: Vechonk<str>,
+}
+
+/// A `Vechonk<[u8]>`, containing arbitrary bytes
+pub struct VechonkBytes {
+    chonk: Vechonk<[u8]>,
+}
+
+/// A borrowed element, which is not null terminated
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VechonkSlice {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+/// An iterator over the elements of a `VechonkStr` or `VechonkBytes`, that lives on the C side.
+/// It must only be used with the `Vechonk` it was created from, and only as long as that isn't pushed to or freed
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VechonkIter {
+    chonk: *const core::ffi::c_void,
+    index: usize,
+}
+
+impl VechonkSlice {
+    fn new(bytes: &[u8]) -> Self {
+        Self {
+            ptr: bytes.as_ptr(),
+            len: bytes.len(),
+        }
+    }
+}
+
+/// Runs `f`, but returns `failure` instead of unwinding into C if it panics
+fn catch_panic<R>(failure: R, f: impl FnOnce() -> R) -> R {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(failure)
+}
+
+/// # Safety
+/// `ptr` must be valid for reads of `len` bytes, or `len` must be 0
+unsafe fn bytes_from_raw<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        // SAFETY: We can assume that `ptr` is valid for `len` bytes
+        unsafe { slice::from_raw_parts(ptr, len) }
+    }
+}
+
+/// Writes the element to `out` if it exists, returns whether it did
+///
+/// # Safety
+/// `out` must be null or valid for writes
+unsafe fn write_elem(elem: Option<&[u8]>, out: *mut VechonkSlice) -> bool {
+    let elem = match elem {
+        Some(elem) => elem,
+        None => return false,
+    };
+
+    if !out.is_null() {
+        // SAFETY: We can assume that `out` is valid for writes
+        unsafe { out.write(VechonkSlice::new(elem)) };
+    }
+
+    true
+}
+
+/// Creates a new empty `VechonkStr`, which has to be freed with `vechonk_str_free`
+#[no_mangle]
+pub extern "C" fn vechonk_str_new() -> *mut VechonkStr {
+    catch_panic(ptr::null_mut(), || {
+        Box::into_raw(Box::new(VechonkStr {
+            chonk: Vechonk::new(),
+        }))
+    })
+}
+
+/// Frees a `VechonkStr` and all of its strings. Does nothing if `chonk` is null
+///
+/// # Safety
+/// `chonk` must be null or come from `vechonk_str_new`, and must not be used afterwards
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_str_free(chonk: *mut VechonkStr) {
+    catch_panic((), || {
+        if !chonk.is_null() {
+            // SAFETY: We can assume that `chonk` comes from `vechonk_str_new`, which uses `Box`
+            drop(unsafe { Box::from_raw(chonk) });
+        }
+    })
+}
+
+/// Copies `len` bytes from `ptr` into a new string at the end. Returns false and pushes nothing
+/// if the bytes are not valid UTF-8, or false if it panicked
+///
+/// # Safety
+/// `chonk` must be valid, and `ptr` must be valid for reads of `len` bytes, or `len` must be 0
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_str_push(
+    chonk: *mut VechonkStr,
+    ptr: *const u8,
+    len: usize,
+) -> bool {
+    catch_panic(false, || {
+        // SAFETY: We can assume that `ptr` is valid for `len` bytes
+        let string = match str::from_utf8(unsafe { bytes_from_raw(ptr, len) }) {
+            Ok(string) => string,
+            Err(_) => return false,
+        };
+
+        // SAFETY: We can assume that `chonk` is valid
+        let chonk = unsafe { &mut (*chonk).chonk };
+
+        // SAFETY: `str` doesn't own anything, so copying its bytes is fine
+        unsafe { chonk.raw.push_raw(string) };
+        chonk.debug_check_invariants();
+
+        true
+    })
+}
+
+/// The amount of strings
+///
+/// # Safety
+/// `chonk` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_str_len(chonk: *const VechonkStr) -> usize {
+    catch_panic(0, || {
+        // SAFETY: We can assume that `chonk` is valid
+        unsafe { (*chonk).chonk.len() }
+    })
+}
+
+/// Writes the string at `index` to `out`. Returns false and leaves `out` untouched if the index is out of bounds
+///
+/// # Safety
+/// `chonk` must be valid, and `out` must be null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_str_get(
+    chonk: *const VechonkStr,
+    index: usize,
+    out: *mut VechonkSlice,
+) -> bool {
+    catch_panic(false, || {
+        // SAFETY: We can assume that `chonk` is valid
+        let elem = unsafe { (*chonk).chonk.get(index) };
+
+        // SAFETY: We can assume that `out` is null or valid
+        unsafe { write_elem(elem.map(str::as_bytes), out) }
+    })
+}
+
+/// Creates an iterator over the strings, starting at the first one
+#[no_mangle]
+pub extern "C" fn vechonk_str_iter(chonk: *const VechonkStr) -> VechonkIter {
+    VechonkIter {
+        chonk: chonk.cast(),
+        index: 0,
+    }
+}
+
+/// Writes the next string to `out` and advances the iterator. Returns false once all strings have been visited
+///
+/// # Safety
+/// `iter` must be valid and come from `vechonk_str_iter`, and `out` must be null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_str_iter_next(
+    iter: *mut VechonkIter,
+    out: *mut VechonkSlice,
+) -> bool {
+    catch_panic(false, || {
+        // SAFETY: We can assume that `iter` is valid and that it belongs to a valid `VechonkStr`
+        let iter = unsafe { &mut *iter };
+        let chonk = iter.chonk.cast::<VechonkStr>();
+
+        // SAFETY: See above, and we can assume that `out` is null or valid
+        let found = unsafe { vechonk_str_get(chonk, iter.index, out) };
+        iter.index += usize::from(found);
+        found
+    })
+}
+
+/// Creates a new empty `VechonkBytes`, which has to be freed with `vechonk_bytes_free`
+#[no_mangle]
+pub extern "C" fn vechonk_bytes_new() -> *mut VechonkBytes {
+    catch_panic(ptr::null_mut(), || {
+        Box::into_raw(Box::new(VechonkBytes {
+            chonk: Vechonk::new(),
+        }))
+    })
+}
+
+/// Frees a `VechonkBytes` and all of its elements. Does nothing if `chonk` is null
+///
+/// # Safety
+/// `chonk` must be null or come from `vechonk_bytes_new`, and must not be used afterwards
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_bytes_free(chonk: *mut VechonkBytes) {
+    catch_panic((), || {
+        if !chonk.is_null() {
+            // SAFETY: We can assume that `chonk` comes from `vechonk_bytes_new`, which uses `Box`
+            drop(unsafe { Box::from_raw(chonk) });
+        }
+    })
+}
+
+/// Copies `len` bytes from `ptr` into a new element at the end. Returns false if it panicked
+///
+/// # Safety
+/// `chonk` must be valid, and `ptr` must be valid for reads of `len` bytes, or `len` must be 0
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_bytes_push(
+    chonk: *mut VechonkBytes,
+    ptr: *const u8,
+    len: usize,
+) -> bool {
+    catch_panic(false, || {
+        // SAFETY: We can assume that `ptr` is valid for `len` bytes
+        let bytes = unsafe { bytes_from_raw(ptr, len) };
+
+        // SAFETY: We can assume that `chonk` is valid
+        let chonk = unsafe { &mut (*chonk).chonk };
+
+        // SAFETY: `u8` doesn't own anything, so copying the bytes is fine
+        unsafe { chonk.raw.push_raw(bytes) };
+        chonk.debug_check_invariants();
+
+        true
+    })
+}
+
+/// The amount of elements
+///
+/// # Safety
+/// `chonk` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_bytes_len(chonk: *const VechonkBytes) -> usize {
+    catch_panic(0, || {
+        // SAFETY: We can assume that `chonk` is valid
+        unsafe { (*chonk).chonk.len() }
+    })
+}
+
+/// Writes the element at `index` to `out`. Returns false and leaves `out` untouched if the index is out of bounds
+///
+/// # Safety
+/// `chonk` must be valid, and `out` must be null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_bytes_get(
+    chonk: *const VechonkBytes,
+    index: usize,
+    out: *mut VechonkSlice,
+) -> bool {
+    catch_panic(false, || {
+        // SAFETY: We can assume that `chonk` is valid
+        let elem = unsafe { (*chonk).chonk.get(index) };
+
+        // SAFETY: We can assume that `out` is null or valid
+        unsafe { write_elem(elem, out) }
+    })
+}
+
+/// Creates an iterator over the elements, starting at the first one
+#[no_mangle]
+pub extern "C" fn vechonk_bytes_iter(chonk: *const VechonkBytes) -> VechonkIter {
+    VechonkIter {
+        chonk: chonk.cast(),
+        index: 0,
+    }
+}
+
+/// Writes the next element to `out` and advances the iterator. Returns false once all elements have been visited
+///
+/// # Safety
+/// `iter` must be valid and come from `vechonk_bytes_iter`, and `out` must be null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn vechonk_bytes_iter_next(
+    iter: *mut VechonkIter,
+    out: *mut VechonkSlice,
+) -> bool {
+    catch_panic(false, || {
+        // SAFETY: We can assume that `iter` is valid and that it belongs to a valid `VechonkBytes`
+        let iter = unsafe { &mut *iter };
+        let chonk = iter.chonk.cast::<VechonkBytes>();
+
+        // SAFETY: See above, and we can assume that `out` is null or valid
+        let found = unsafe { vechonk_bytes_get(chonk, iter.index, out) };
+        iter.index += usize::from(found);
+        found
+    })
+}
@@ -0,0 +1,64 @@
+//! Policies for how much a `Vechonk` grows when it runs out of space
+
+use core::cmp;
+
+/// Decides the new capacity of a [`Vechonk`](crate::Vechonk) when its buffer is too small.
+///
+/// A strategy is set with [`Vechonk::with_capacity_and_strategy`](crate::Vechonk::with_capacity_and_strategy) or
+/// [`Vechonk::set_growth_strategy`](crate::Vechonk::set_growth_strategy), and stored as a `&'static` reference,
+/// so the built-in ones and any constant can be used directly, like `&Increment(4096)`.
+/// A strategy computed at runtime has to be leaked with [`alloc::boxed::Box::leak`].
+///
+/// The default is [`Doubling`]
+pub trait GrowthStrategy: Sync {
+    /// The new capacity in bytes, for a buffer with a capacity of `current` bytes that needs at least
+    /// `required` bytes to fit everything.
+    ///
+    /// Returning less than `required` is treated like returning `required`. The result is rounded up to a
+    /// multiple of the alignment of the metadata.
+    fn grow(&self, current: usize, required: usize) -> usize;
+}
+
+/// Doubles the capacity, or grows to the required capacity if that's more.
+/// This is the default, like for [`alloc::vec::Vec`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Doubling;
+
+/// Grows the capacity by half of it, or to the required capacity if that's more.
+/// Wastes less memory than [`Doubling`], but needs more regrows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OneAndAHalf;
+
+/// Grows the capacity by a fixed amount of bytes, or to the required capacity if that's more.
+/// This gives a predictable upper bound for the unused memory, at the cost of regrowing O(n) times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Increment(pub usize);
+
+/// Grows the capacity to exactly what is required, so there is almost no unused memory, but every push
+/// that doesn't fit regrows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Exact;
+
+impl GrowthStrategy for Doubling {
+    fn grow(&self, current: usize, required: usize) -> usize {
+        cmp::max(current.saturating_mul(2), required)
+    }
+}
+
+impl GrowthStrategy for OneAndAHalf {
+    fn grow(&self, current: usize, required: usize) -> usize {
+        cmp::max(current.saturating_add(current / 2), required)
+    }
+}
+
+impl GrowthStrategy for Increment {
+    fn grow(&self, current: usize, required: usize) -> usize {
+        cmp::max(current.saturating_add(self.0), required)
+    }
+}
+
+impl GrowthStrategy for Exact {
+    fn grow(&self, _current: usize, required: usize) -> usize {
+        required
+    }
+}
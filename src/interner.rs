@@ -0,0 +1,175 @@
+//! A string interner, that stores the strings in a [`Vechonk<str>`], behind the `interner` feature
+//!
+//! ```
+//! use vechonk::interner::Interner;
+//!
+//! let mut interner = Interner::new();
+//!
+//! let hello = interner.intern("hello");
+//! let uwu = interner.intern("uwu");
+//!
+//! assert_eq!(interner.intern("hello"), hello);
+//! assert_eq!(interner.resolve(uwu), "uwu");
+//! ```
+
+use crate::Vechonk;
+use core::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use hashbrown::raw::RawTable;
+
+/// A handle to an interned string, that can be resolved with the [`Interner`] it came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The index of the string in the [`Interner`], symbols are handed out in order starting from 0
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+/// Deduplicates strings and hands out [`Symbol`]s for them.
+///
+/// Every string is stored exactly once in a [`Vechonk<str>`]. The hash table only contains the indices into
+/// the `Vechonk`, so strings are not copied a second time.
+///
+/// By default, this uses a fast hash function that is not resistant against HashDoS attacks.
+/// Use [`Interner::with_hasher`] if the strings come from untrusted input.
+pub struct Interner<S = BuildHasherDefault<FxHasher>> {
+    strings: Vechonk<str>,
+    table: RawTable<u32>,
+    hash_builder: S,
+}
+
+impl Interner {
+    /// Create a new empty `Interner` that doesn't allocate anything
+    pub fn new() -> Self {
+        Self::with_hasher(BuildHasherDefault::default())
+    }
+}
+
+impl<S: BuildHasher> Interner<S> {
+    /// Create a new empty `Interner` that uses `hash_builder` to hash the strings
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            strings: Vechonk::new(),
+            table: RawTable::new(),
+            hash_builder,
+        }
+    }
+
+    /// The amount of distinct strings that have been interned, O(1)
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether no strings have been interned yet, O(1)
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Get the symbol for `string`, storing it if it hasn't been interned before
+    ///
+    /// Panics if more than `u32::MAX` strings are interned
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        let hash = hash_str(&self.hash_builder, string);
+        let strings = &self.strings;
+
+        if let Some(&index) = self
+            .table
+            .get(hash, |&index| &strings[index as usize] == string)
+        {
+            return Symbol(index);
+        }
+
+        let index = u32::try_from(self.strings.len()).expect("Interned more than u32::MAX strings");
+
+        // SAFETY: `str` has no destructor and doesn't own anything, so copying its bytes is fine
+        unsafe { self.strings.raw.push_raw(string) };
+        self.strings.debug_check_invariants();
+
+        let (strings, hash_builder) = (&self.strings, &self.hash_builder);
+        self.table.insert(hash, index, |&index| {
+            hash_str(hash_builder, &strings[index as usize])
+        });
+
+        Symbol(index)
+    }
+
+    /// Get the symbol for `string` without interning it. Returns `None` if it hasn't been interned yet
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        let hash = hash_str(&self.hash_builder, string);
+
+        self.table
+            .get(hash, |&index| &self.strings[index as usize] == string)
+            .map(|&index| Symbol(index))
+    }
+
+    /// Get the string for a symbol
+    ///
+    /// Panics if the symbol comes from a different `Interner` and is out of bounds
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+
+    /// The interned strings, indexed by [`Symbol::as_u32`]
+    pub fn strings(&self) -> &Vechonk<str> {
+        &self.strings
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn hash_str<S: BuildHasher>(hash_builder: &S, string: &str) -> u64 {
+    let mut hasher = hash_builder.build_hasher();
+    string.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The hash function used by rustc, see <https://github.com/rust-lang/rustc-hash>.
+/// It's very fast, but not resistant against HashDoS attacks
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_ne_bytes(chunk.try_into().unwrap()));
+        }
+
+        for &byte in chunks.remainder() {
+            self.add_to_hash(u64::from(byte));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
@@ -0,0 +1,145 @@
+//! Checks for the invariants of the buffer layout that the unsafe code relies on
+
+use crate::raw::PtrData;
+use crate::Vechonk;
+use alloc::vec::Vec;
+use core::{fmt, mem, ptr};
+
+/// An invariant of the buffer layout that doesn't hold, found by [`Vechonk::check_invariants`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The capacity is not a multiple of the alignment of the metadata
+    MisalignedCapacity,
+    /// The element section and the metadata don't fit into the capacity together, so they overlap
+    MetadataOverlap,
+    /// The element at the index ends after the end of the element section, or after the end of the buffer
+    /// if it's zero sized
+    ElementOutOfBounds(usize),
+    /// The element at the index is not aligned for its type
+    MisalignedElement(usize),
+    /// The elements at the two indices overlap in memory
+    ElementOverlap(usize, usize),
+    /// The element at the index is laid out in memory in front of a previous element, even though the elements are
+    /// marked as not having been reordered
+    ElementOutOfOrder(usize),
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MisalignedCapacity => f.write_str("capacity is not aligned for the metadata"),
+            Self::MetadataOverlap => f.write_str("elements and metadata overlap"),
+            Self::ElementOutOfBounds(index) => {
+                write!(f, "element {} ends after the element section", index)
+            }
+            Self::MisalignedElement(index) => write!(f, "element {} is not aligned", index),
+            Self::ElementOverlap(a, b) => write!(f, "elements {} and {} overlap", a, b),
+            Self::ElementOutOfOrder(index) => {
+                write!(f, "element {} is in front of a previous element", index)
+            }
+        }
+    }
+}
+
+impl<T: ?Sized> Vechonk<T> {
+    /// Checks that the layout of the buffer is consistent, O(n log n)
+    /// * The element section and the metadata fit into the capacity
+    /// * Every element lies inside of the element section and is aligned for itself. Zero sized elements only have to
+    ///   lie inside of the buffer
+    /// * The offsets are monotonic in the order the elements are laid out in memory, so no two elements overlap.
+    ///   Zero sized elements can share their offset with other elements.
+    /// * Unless the elements have been reordered, the ones that aren't zero sized are laid out in the order
+    ///   of their indices
+    ///
+    /// This always holds unless the `Vechonk` was created from invalid raw parts, or there is a bug in this crate.
+    /// With the `debug-invariants` feature, it's checked after every mutation, panicking if it fails.
+    ///
+    /// The metadata itself is trusted to be valid for `T`, since the size and alignment are computed from it.
+    /// The elements themselves are never read or referenced, so offsets pointing outside of the buffer are reported
+    /// instead of being followed
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        let raw = &self.raw;
+
+        if raw.cap % mem::align_of::<PtrData<T>>() != 0 {
+            return Err(InvariantError::MisalignedCapacity);
+        }
+
+        match raw.elem_size.checked_add(raw.data_section_size()) {
+            Some(used) if used <= raw.cap => {}
+            _ => return Err(InvariantError::MetadataOverlap),
+        }
+
+        let mut elems = Vec::with_capacity(self.len());
+
+        for index in 0..self.len() {
+            // SAFETY: The index is in bounds, and the metadata section has been checked to lie inside of the buffer
+            let (offset, meta) = unsafe { (raw.get_offset(index), raw.get_meta(index)) };
+
+            if offset > raw.cap {
+                return Err(InvariantError::ElementOutOfBounds(index));
+            }
+
+            // the pointer is never dereferenced, the size and alignment are computed from the metadata only
+            let elem_ptr =
+                ptr::from_raw_parts::<T>(raw.ptr.as_ptr().wrapping_add(offset).cast(), meta);
+            // SAFETY: The metadata is trusted to be valid for `T`, see above
+            let (size, align) = unsafe {
+                (
+                    mem::size_of_val_raw(elem_ptr),
+                    mem::align_of_val_raw(elem_ptr),
+                )
+            };
+
+            // zero sized elements can be left behind the end of the element section when it shrinks, which is fine
+            // as long as they stay inside of the buffer
+            let limit = if size == 0 { raw.cap } else { raw.elem_size };
+
+            match offset.checked_add(size) {
+                Some(end) if end <= limit => {}
+                _ => return Err(InvariantError::ElementOutOfBounds(index)),
+            }
+
+            if raw.ptr.as_ptr().addr().wrapping_add(offset) % align != 0 {
+                return Err(InvariantError::MisalignedElement(index));
+            }
+
+            if size > 0 {
+                elems.push((offset, size, index));
+            }
+        }
+
+        // the elements are still in the order of their indices here
+        let out_of_order = elems
+            .windows(2)
+            .find(|pair| pair[0].0 > pair[1].0)
+            .map(|pair| pair[1].2);
+
+        elems.sort_unstable();
+
+        elems.windows(2).try_for_each(|pair| {
+            let ((prev_offset, prev_size, prev_index), (offset, _, index)) = (pair[0], pair[1]);
+
+            if prev_offset + prev_size > offset {
+                Err(InvariantError::ElementOverlap(prev_index, index))
+            } else {
+                Ok(())
+            }
+        })?;
+
+        // the elements don't overlap, so it's enough to check that the offsets are increasing
+        match out_of_order {
+            Some(index) if raw.in_order => Err(InvariantError::ElementOutOfOrder(index)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Panics if [`Vechonk::check_invariants`] fails with the `debug-invariants` feature, does nothing otherwise.
+    /// Called at the end of every method that changes the layout of the buffer
+    #[inline]
+    pub(crate) fn debug_check_invariants(&self) {
+        #[cfg(feature = "debug-invariants")]
+        if let Err(err) = self.check_invariants() {
+            panic!("Vechonk invariant violated: {}", err);
+        }
+    }
+}
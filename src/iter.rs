@@ -1,9 +1,7 @@
-use crate::{RawVechonk, Vechonk};
-use alloc::boxed::Box;
+use crate::raw::RawVechonk;
 use core::marker::PhantomData;
-use core::mem::ManuallyDrop;
 
-/// An iterator over the elements of a [`Vechonk`]
+/// An iterator over the elements of a [`Vechonk`](crate::Vechonk)
 pub struct Iter<'a, T: ?Sized> {
     raw: RawVechonk<T>,
     current_index: usize,
@@ -11,13 +9,37 @@ pub struct Iter<'a, T: ?Sized> {
 }
 
 impl<'a, T: ?Sized> Iter<'a, T> {
-    pub(super) fn new(chonk: &'a Vechonk<T>) -> Iter<'a, T> {
+    /// `raw` must be a view into a buffer that is borrowed for `'a`
+    pub(crate) fn from_raw(raw: RawVechonk<T>) -> Self {
         Self {
-            raw: chonk.raw.copy(),
+            raw,
             current_index: 0,
             _marker: PhantomData,
         }
     }
+
+    /// Splits the remaining elements into two iterators at `index`, relative to the current position
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.current_index + index;
+
+        // the copied `raw` is only used as a view into the `Vechonk`, so its length is the end of the iterator
+        let mut left_raw = self.raw.copy();
+        left_raw.len = mid;
+
+        let left = Self {
+            raw: left_raw,
+            current_index: self.current_index,
+            _marker: PhantomData,
+        };
+        let right = Self {
+            raw: self.raw,
+            current_index: mid,
+            _marker: PhantomData,
+        };
+
+        (left, right)
+    }
 }
 
 impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
@@ -50,61 +72,32 @@ impl<'a, T: ?Sized> ExactSizeIterator for Iter<'a, T> {
     }
 }
 
-/// An iterator over the elements of a [`Vechonk`]
-pub struct IterMut<'a, T: ?Sized> {
-    raw: RawVechonk<T>,
-    current_index: usize,
-    _marker: PhantomData<&'a T>,
-}
-
-impl<'a, T: ?Sized> IterMut<'a, T> {
-    pub(super) fn new(chonk: &'a mut Vechonk<T>) -> IterMut<'a, T> {
-        Self {
-            raw: chonk.raw.copy(),
-            current_index: 0,
-            _marker: PhantomData,
-        }
-    }
-}
-
-impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<'a, T: ?Sized> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
         if self.current_index == self.raw.len {
             return None;
         }
 
-        // SAFETY: We just did a bounds check above
-        let ptr = unsafe { self.raw.get_unchecked_ptr(self.current_index) };
+        // the copied `raw` is only used as a view, so shrinking it doesn't affect the `Vechonk`
+        self.raw.len -= 1;
 
-        self.current_index += 1;
+        // SAFETY: We just did a bounds check above
+        let ptr = unsafe { self.raw.get_unchecked_ptr(self.raw.len) };
 
         // SAFETY: We rely on `get_unchecked_ptr` returning a valid pointer, which is does, see its SAFETY comments
-        unsafe { Some(&mut *ptr) }
-    }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let count = self.raw.len - self.current_index;
-
-        (count, Some(count))
-    }
-}
-
-impl<'a, T: ?Sized> ExactSizeIterator for IterMut<'a, T> {
-    fn len(&self) -> usize {
-        self.raw.len - self.current_index
+        unsafe { Some(&*ptr) }
     }
 }
 
-/// An iterator over the elements of a [`Vechonk`]
-pub struct IntoIter<T: ?Sized> {
+/// An iterator over the elements of a [`Vechonk`](crate::Vechonk)
+pub struct IterMut<'a, T: ?Sized> {
     raw: RawVechonk<T>,
     current_index: usize,
-    _marker: PhantomData<T>,
+    _marker: PhantomData<&'a T>,
 }
 
-impl<T: ?Sized> IntoIter<T> {
+impl<'a, T: ?Sized> IterMut<'a, T> {
+    /// `raw` must be a view into a buffer that is borrowed for `'a`
     pub(crate) fn from_raw(raw: RawVechonk<T>) -> Self {
         Self {
             raw,
@@ -113,17 +106,32 @@ impl<T: ?Sized> IntoIter<T> {
         }
     }
 
-    pub(crate) fn new(chonk: Vechonk<T>) -> IntoIter<T> {
-        // We don't want to free the memory yet!
-        let chonk = ManuallyDrop::new(chonk);
-        let raw = chonk.raw.copy();
+    /// Splits the remaining elements into two iterators at `index`, relative to the current position
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.current_index + index;
 
-        Self::from_raw(raw)
+        // the copied `raw` is only used as a view into the `Vechonk`, so its length is the end of the iterator
+        let mut left_raw = self.raw.copy();
+        left_raw.len = mid;
+
+        let left = Self {
+            raw: left_raw,
+            current_index: self.current_index,
+            _marker: PhantomData,
+        };
+        let right = Self {
+            raw: self.raw,
+            current_index: mid,
+            _marker: PhantomData,
+        };
+
+        (left, right)
     }
 }
 
-impl<T: ?Sized> Iterator for IntoIter<T> {
-    type Item = Box<T>;
+impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current_index == self.raw.len {
@@ -131,12 +139,12 @@ impl<T: ?Sized> Iterator for IntoIter<T> {
         }
 
         // SAFETY: We just did a bounds check above
-        //         We also increment the `current_index`, to make sure that we never access it again
-        let ptr = unsafe { self.raw.box_elem_unchecked(self.current_index) };
+        let ptr = unsafe { self.raw.get_unchecked_ptr(self.current_index) };
 
         self.current_index += 1;
 
-        Some(ptr)
+        // SAFETY: We rely on `get_unchecked_ptr` returning a valid pointer, which is does, see its SAFETY comments
+        unsafe { Some(&mut *ptr) }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -146,17 +154,25 @@ impl<T: ?Sized> Iterator for IntoIter<T> {
     }
 }
 
-impl<T: ?Sized> ExactSizeIterator for IntoIter<T> {
+impl<'a, T: ?Sized> ExactSizeIterator for IterMut<'a, T> {
     fn len(&self) -> usize {
         self.raw.len - self.current_index
     }
 }
 
-impl<T: ?Sized> Drop for IntoIter<T> {
-    fn drop(&mut self) {
-        // SAFETY: We as `Vechonk` do own the data, and it has the length `self.raw.cap`
-        unsafe {
-            RawVechonk::<T>::dealloc(self.raw.cap, self.raw.ptr.as_ptr());
+impl<'a, T: ?Sized> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_index == self.raw.len {
+            return None;
         }
+
+        // the copied `raw` is only used as a view, so shrinking it doesn't affect the `Vechonk`
+        self.raw.len -= 1;
+
+        // SAFETY: We just did a bounds check above
+        let ptr = unsafe { self.raw.get_unchecked_ptr(self.raw.len) };
+
+        // SAFETY: We rely on `get_unchecked_ptr` returning a valid pointer, which is does, see its SAFETY comments
+        unsafe { Some(&mut *ptr) }
     }
 }
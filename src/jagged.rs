@@ -0,0 +1,127 @@
+//! Row operations for using a `Vechonk<[T]>` as a jagged 2D array
+
+use crate::{Iter, IterMut, Vechonk};
+use core::iter::Flatten;
+use core::{mem, ptr};
+
+impl<T> Vechonk<[T]> {
+    /// Pushes a new row containing clones of the items of `row`
+    pub fn push_row(&mut self, row: &[T])
+    where
+        T: Clone,
+    {
+        self.push_row_iter(row.iter().cloned())
+    }
+
+    /// Pushes a new row containing the items of `row`. The space for the row is reserved up front,
+    /// and the items are written into the `Vechonk` directly, without boxing the row first
+    pub fn push_row_iter<I>(&mut self, row: I)
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut row = row.into_iter();
+        let len = row.len();
+
+        // with the space for the items reserved, the empty row ends up at the end of the element section,
+        // so the items can be written behind it without regrowing or moving it
+        self.reserve_for(
+            1,
+            mem::size_of::<T>()
+                .checked_mul(len)
+                .expect("capacity overflow"),
+        );
+
+        let empty: &[T] = &[];
+        // SAFETY: An empty slice doesn't contain anything that could be used again
+        unsafe { self.raw.push_raw(empty) };
+        let index = self.len() - 1;
+
+        // SAFETY: We just pushed the row, so the index is in bounds
+        let offset = unsafe { self.raw.get_offset(index) };
+
+        for (i, item) in row.by_ref().take(len).enumerate() {
+            // SAFETY: We have reserved space for `len` items behind the row, and the offset is aligned for `T`.
+            //         The metadata is updated after every item, so only initialized items are part of the row
+            unsafe {
+                let items = self.raw.ptr.as_ptr().add(offset).cast::<T>();
+                ptr::write(items.add(i), item);
+                self.raw.set_meta(index, i + 1);
+            }
+            self.raw.elem_size = offset + mem::size_of::<T>() * (i + 1);
+        }
+
+        // the iterator might have lied about its length
+        if let Some(mut elem) = self.get_resizable(index) {
+            row.for_each(|item| elem.push(item));
+        }
+
+        self.debug_check_invariants();
+    }
+
+    /// The amount of items in a row. This only reads the metadata, not the row itself, O(1)
+    ///
+    /// Panics if the index is out of bounds
+    pub fn row_len(&self, index: usize) -> usize {
+        if index >= self.len() {
+            panic!("Out of bounds, index {} for len {}", index, self.len());
+        }
+
+        // SAFETY: We just did a bounds check
+        unsafe { self.raw.get_meta(index) }
+    }
+
+    /// The amount of items in all rows combined, O(n)
+    pub fn total_items(&self) -> usize {
+        // SAFETY: All indices are in bounds
+        (0..self.len())
+            .map(|index| unsafe { self.raw.get_meta(index) })
+            .sum()
+    }
+
+    /// An iterator over the items of all rows, row by row
+    pub fn flat_iter(&self) -> Flatten<Iter<'_, [T]>> {
+        self.iter().flatten()
+    }
+
+    /// An iterator over the rows yielding mutable references. The rows never overlap,
+    /// so all of them can be held and used at the same time, for example from different threads
+    pub fn rows_mut(&mut self) -> IterMut<'_, [T]> {
+        self.iter_mut()
+    }
+
+    /// Appends clones of `items` to the end of a row.
+    ///
+    /// The row grows in place if there is free space behind it, which is always the case for the row at the end
+    /// of the buffer, like the one that was pushed last. Growing that one is O(1) unless it regrows, other rows
+    /// have to find the free space around them first, like [`Vechonk::try_replace`].
+    /// If there isn't enough space, the row is moved to the end of the `Vechonk` first, which might regrow it.
+    ///
+    /// Panics if the index is out of bounds
+    pub fn extend_row(&mut self, index: usize, items: &[T])
+    where
+        T: Clone,
+    {
+        let len = self.row_len(index);
+
+        // SAFETY: `row_len` has checked the index
+        let offset = unsafe {
+            self.raw
+                .reserve_elem(index, mem::size_of::<T>() * (len + items.len()))
+        };
+
+        for (i, item) in items.iter().enumerate() {
+            let item = item.clone();
+
+            // SAFETY: We have reserved space for the new items, and the offset is aligned for `T`.
+            //         The metadata is updated after every item, so only initialized items are part of the row
+            unsafe {
+                let row = self.raw.ptr.as_ptr().add(offset).cast::<T>();
+                ptr::write(row.add(len + i), item);
+                self.raw.set_meta(index, len + i + 1);
+            }
+        }
+
+        self.debug_check_invariants();
+    }
+}
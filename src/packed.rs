@@ -0,0 +1,128 @@
+use crate::raw::PtrData;
+use crate::{force_align, Vechonk};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{cmp, mem};
+
+/// Builds a [`Vechonk`] whose elements are laid out grouped by alignment, to minimize the padding between them
+///
+/// [`Vechonk::push`] has to insert padding whenever an element needs a bigger alignment than the end of the
+/// previous element provides. The builder collects all elements first, and then lays them out by descending
+/// alignment, so that only the first element can need padding. The order of the indices is kept, only the
+/// elements are moved in memory.
+///
+/// ```
+/// use core::any::Any;
+/// use vechonk::PackedBuilder;
+///
+/// #[repr(align(64))]
+/// struct Big(u8);
+///
+/// let mut builder = PackedBuilder::<dyn Any>::new();
+/// builder.push(Box::new(1_u8));
+/// builder.push(Box::new(Big(2)));
+/// builder.push(Box::new(3_u8));
+/// builder.push(Box::new(Big(4)));
+///
+/// let (chonk, report) = builder.build();
+///
+/// assert_eq!(chonk[1].downcast_ref::<Big>().unwrap().0, 2);
+/// assert!(report.padding < report.naive_padding);
+/// ```
+pub struct PackedBuilder<T: ?Sized> {
+    /// The elements laid out like `push` would, which is also used to compare the padding
+    chonk: Vechonk<T>,
+}
+
+/// How much padding a [`PackedBuilder`] saved, compared to pushing the elements one by one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackReport {
+    /// The padding between the elements in the packed `Vechonk` in bytes
+    pub padding: usize,
+    /// The padding between the elements if they had been pushed in order in bytes
+    pub naive_padding: usize,
+}
+
+impl PackReport {
+    /// The bytes saved by packing
+    pub fn saved(&self) -> usize {
+        self.naive_padding.saturating_sub(self.padding)
+    }
+}
+
+impl<T: ?Sized> PackedBuilder<T> {
+    /// Create a new empty `PackedBuilder` that doesn't allocate anything
+    pub const fn new() -> Self {
+        Self {
+            chonk: Vechonk::new(),
+        }
+    }
+
+    /// The amount of elements that have been added, O(1)
+    pub fn len(&self) -> usize {
+        self.chonk.len()
+    }
+
+    /// Whether no elements have been added yet, O(1)
+    pub fn is_empty(&self) -> bool {
+        self.chonk.is_empty()
+    }
+
+    /// Adds an element, it will have the next index in the built `Vechonk`
+    pub fn push(&mut self, element: Box<T>) {
+        self.chonk.push(element)
+    }
+
+    /// The elements that have been added so far, laid out like [`Vechonk::push`] would before they are packed
+    pub fn as_vechonk(&self) -> &Vechonk<T> {
+        &self.chonk
+    }
+
+    /// Lays out the elements by descending alignment in a new allocation that fits them exactly,
+    /// and reports the padding that was saved. O(n log n)
+    pub fn build(self) -> (Vechonk<T>, PackReport) {
+        let mut chonk = self.chonk;
+
+        let naive = chonk.memory_report();
+
+        let mut order = (0..chonk.len()).collect::<Vec<_>>();
+        // SAFETY: All indices are in bounds
+        order.sort_by_key(|&i| cmp::Reverse(mem::align_of_val(unsafe { chonk.get_unchecked(i) })));
+
+        // sizes are multiples of their alignment, so with descending alignments, only the first element
+        // might need padding, if the allocation is less aligned than it
+        let max_align = order
+            .first()
+            // SAFETY: All indices are in bounds
+            .map_or(1, |&i| mem::align_of_val(unsafe { chonk.get_unchecked(i) }));
+        // the allocation is aligned for the metadata
+        let data_align = mem::align_of::<PtrData<T>>();
+        let capacity =
+            naive.element_bytes + max_align.saturating_sub(data_align) + naive.metadata_bytes;
+
+        // the capacity is shrunken down to a multiple of the alignment of the metadata, so round it up first
+        let capacity = force_align(capacity + data_align - 1, data_align);
+
+        chonk.raw.relocate_in_order(capacity, &order);
+        chonk.debug_check_invariants();
+
+        let report = PackReport {
+            padding: chonk.memory_report().wasted_bytes,
+            naive_padding: naive.wasted_bytes,
+        };
+
+        (chonk, report)
+    }
+}
+
+impl<T: ?Sized> Default for PackedBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> Extend<Box<T>> for PackedBuilder<T> {
+    fn extend<I: IntoIterator<Item = Box<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|element| self.push(element));
+    }
+}
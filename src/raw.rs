@@ -1,12 +1,18 @@
-use crate::force_align;
-use alloc::boxed::Box;
-use core::alloc::Layout;
+use crate::growth::{Doubling, GrowthStrategy};
 use core::marker::PhantomData;
-use core::num::NonZeroUsize;
 use core::ptr::{NonNull, Pointee};
 use core::{mem, ptr};
+#[cfg(feature = "alloc")]
+use {
+    crate::{force_align, KnownAlign},
+    alloc::{boxed::Box, vec, vec::Vec},
+    core::{alloc::Layout, cmp, num::NonZeroUsize, slice},
+};
 
 /// The offset + metadata for each element, stored at the end
+///
+/// This is `repr(C)`, since its layout is documented in [`crate::Vechonk::from_raw_parts`]
+#[repr(C)]
 pub struct PtrData<T: ?Sized> {
     offset: usize,
     meta: <T as Pointee>::Metadata,
@@ -31,6 +37,11 @@ pub struct RawVechonk<T: ?Sized> {
     pub cap: usize,
     /// How much memory has been used by the elements, where the next element starts
     pub elem_size: usize,
+    /// How much to grow when the allocation is too small
+    pub growth: &'static dyn GrowthStrategy,
+    /// Whether the elements that aren't zero sized are laid out in memory in the order of their indices.
+    /// This is cleared by reordering, and allows finding the free space around an element in O(1)
+    pub in_order: bool,
     pub _marker: PhantomData<T>,
 }
 
@@ -42,6 +53,8 @@ impl<T: ?Sized> RawVechonk<T> {
             len: self.len,
             cap: self.cap,
             elem_size: self.elem_size,
+            growth: self.growth,
+            in_order: self.in_order,
             _marker: PhantomData,
         }
     }
@@ -52,35 +65,31 @@ impl<T: ?Sized> RawVechonk<T> {
             len: 0,
             cap: 0,
             elem_size: 0,
+            growth: &Doubling,
+            in_order: true,
             _marker: PhantomData,
         }
     }
 
-    pub fn with_capacity(capacity: usize) -> Self {
-        let capacity = force_align(capacity, Self::data_align());
-
-        let mut vechonk = Self::new();
-
-        if capacity == 0 {
-            return vechonk;
-        }
-
-        vechonk.reset_alloc(NonZeroUsize::new(capacity).unwrap());
-        vechonk
-    }
-
-    pub fn push(&mut self, element: Box<T>) {
-        let elem_size = mem::size_of_val(element.as_ref());
-
-        let elem_align = mem::align_of_val(element.as_ref());
-        let elem_ptr = Box::into_raw(element);
+    /// Copies the element behind `elem_ptr` to the end of the `RawVechonk` if it fits without regrowing.
+    /// Returns whether it was pushed
+    /// # Safety
+    /// `elem_ptr` must point to a valid `T` that doesn't live inside this `RawVechonk`.
+    /// If it was pushed, the value is moved into the `RawVechonk`, so it must not be used or dropped afterwards
+    pub unsafe fn try_push_raw(&mut self, elem_ptr: *const T) -> bool {
+        // SAFETY: We can assume that `elem_ptr` points to a valid `T`
+        let elem_size = mem::size_of_val(unsafe { &*elem_ptr });
+        let elem_align = mem::align_of_val(unsafe { &*elem_ptr });
 
         let data_size = mem::size_of::<PtrData<T>>();
-        let elem_offset = self.elem_size;
 
         // SAFETY: `self.elem_size` can't be longer than the allocation, because `PtrData<T>` needs space as well
-        let required_align_offset =
-            unsafe { self.ptr.as_ptr().add(elem_offset).align_offset(elem_align) };
+        let required_align_offset = unsafe {
+            self.ptr
+                .as_ptr()
+                .add(self.elem_size)
+                .align_offset(elem_align)
+        };
 
         if required_align_offset == usize::MAX {
             panic!(
@@ -89,39 +98,384 @@ impl<T: ?Sized> RawVechonk<T> {
             );
         }
 
-        // just panic here instead of a proper realloc
         if self.needs_grow(elem_size + data_size + required_align_offset) {
-            // If we need to grow, we can be sure that at least some part of it is not zero
-            self.regrow(unsafe { NonZeroUsize::new_unchecked(self.cap + elem_size + data_size) });
+            return false;
         }
 
-        // Copy the element to the new location
-        // Calculate the dest pointer again because we might have realloced
-        // SAFETY: `self.elem_size` can't be longer than the allocation, because `PtrData<T>` needs space as well
+        let elem_offset = self.elem_size + required_align_offset;
+
+        // SAFETY: We have made sure above that there is enough space for the element at `elem_offset`
         let dest_ptr = unsafe { self.ptr.as_ptr().add(elem_offset) };
-        let dest_align_offset = dest_ptr.align_offset(elem_align);
-        let dest_ptr = unsafe { dest_ptr.add(dest_align_offset) };
 
-        // SAFETY: `elem_ptr` comes from `Box`, and is therefore valid to read from for the size
+        // SAFETY: `elem_ptr` points to a valid `T`, and is therefore valid to read from for the size
         //         We have made sure above that we have more than `elem_size` bytes free
-        //         The two allocations cannot overlap, since the `Box` owned its contents, and so do we
+        //         The two allocations cannot overlap, since the caller owned the value, and we own our memory
         //         `dest_ptr` has been aligned above
         unsafe {
             ptr::copy_nonoverlapping::<u8>(elem_ptr as _, dest_ptr, elem_size);
         }
 
         // SAFETY: We've made sure that there's enough space for another data
-        unsafe { self.write_meta_data(elem_ptr, elem_offset + dest_align_offset, self.len) };
+        unsafe { self.write_meta_data(elem_ptr as *mut T, elem_offset, self.len) };
 
-        self.elem_size += elem_size;
+        self.elem_size = elem_offset + elem_size;
         self.len += 1;
 
+        true
+    }
+
+    /// Removes the last element without reading or dropping it
+    /// # Safety
+    /// The `RawVechonk` must not be empty. The element is not dropped, so it must have been moved out before
+    pub unsafe fn remove_last(&mut self) {
+        // SAFETY: We can assume that `self.len` is not 0, so it's in bounds
+        let data = unsafe { self.get_data(self.len - 1) };
+        // SAFETY: The bytes are still there, even if the value has been moved out, and only the metadata is used
+        let elem_size = mem::size_of_val::<T>(unsafe { &*self.get_unchecked_ptr(self.len - 1) });
+
+        // The last element doesn't have to be the one at the end of the element section, since the
+        // `PtrData` can be reordered. We can only reclaim its memory if nothing comes after it.
+        if elem_size != 0 && data.offset + elem_size == self.elem_size {
+            self.elem_size = data.offset;
+        }
+
+        // We don't need to care about our memory, we can just decrement the `len` and let the old memory be, it's
+        // now semantically uninitialized
+        self.len -= 1;
+    }
+
+    /// Get a raw ptr to an element. Be careful about casting this into a `mut &T`
+    /// # SAFETY
+    /// The index must be in bounds
+    pub unsafe fn get_unchecked_ptr(&self, index: usize) -> *mut T {
+        // SAFETY: We can assume that `index` is valid
+        let data = unsafe { self.get_data(index) };
+
+        let elem_ptr = unsafe { self.ptr.as_ptr().add(data.offset) };
+
+        ptr::from_raw_parts_mut(elem_ptr as *mut (), data.meta)
+    }
+
+    pub fn check_index(&self, index: usize) {
+        if index >= self.len {
+            panic!("Out of bounds, index {} for len {}", index, self.len);
+        }
+    }
+
+    /// Writes the metadata of the `ptr` and the `offset` to a `PtrData<T>` at `index`.
+    /// This only needs a shared reference, since it writes through `self.ptr`
+    /// # Safety
+    /// `index` must be in bounds, or only so much out of bounds as to not overwrite element data.
+    /// No one else may access the `PtrData<T>` at `index` at the same time
+    pub unsafe fn write_meta_data(&self, ptr: *mut T, offset: usize, index: usize) {
+        // SAFETY: `index` is not out of bounds, and we are overwriting the element afterwards
+        let data_ptr = unsafe { self.get_data_ptr(index) };
+
+        let meta = ptr::metadata::<T>(ptr);
+
+        let new_data: PtrData<T> = PtrData { offset, meta };
+
+        // SAFETY: We can assume that `get_data_ptr` returns valid pointers to `PtrData<T>`
+        unsafe { *data_ptr = new_data };
+    }
+
+    /// Get the data for the index
+    /// # Safety
+    /// `index` must not be out of bounds
+    unsafe fn get_data(&self, index: usize) -> PtrData<T> {
+        // SAFETY: We can assume that `index` is not out of bounds
+        let data_ptr = unsafe { self.get_data_ptr(index) };
+
+        // SAFETY: The pointer is aligned because `self.ptr` is aligned and `data_offset` is a multiple of the alignment
+        //         The value behind it is always a `PtrData<T>`
+        unsafe { *data_ptr }
+    }
+
+    /// # Safety
+    /// `index` must be in bounds, or only so much out of bounds to not overwrite element data
+    unsafe fn get_data_ptr(&self, index: usize) -> *mut PtrData<T> {
+        let data_offset = self.offset_for_data(index);
+
+        // SAFETY: The offset will always be less than `self.cap`, because we can't have more than `self.len` `PtrData`
+        let data_ptr = unsafe { self.ptr.as_ptr().add(data_offset) };
+        data_ptr as *mut PtrData<T>
+    }
+
+    /// Returns a multiple of the alignment of `PtrData<T>`, since `self.cap` is one, and so is the size
+    const fn offset_for_data(&self, index: usize) -> usize {
+        self.cap
+            .saturating_sub(mem::size_of::<PtrData<T>>() * (index + 1))
+    }
+
+    fn needs_grow(&self, additional_size: usize) -> bool {
+        additional_size > self.cap - (self.elem_size + self.data_section_size())
+    }
+
+    pub const fn data_section_size(&self) -> usize {
+        self.len * mem::size_of::<PtrData<T>>()
+    }
+}
+
+// Everything that allocates, and the parts that only the allocating front ends need
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> RawVechonk<T> {
+    /// Whether `elem` can be pushed without regrowing
+    pub fn has_space_for(&self, elem: &T) -> bool {
+        let elem_align = mem::align_of_val(elem);
+        let required_align_offset = self
+            .ptr
+            .as_ptr()
+            .wrapping_add(self.elem_size)
+            .align_offset(elem_align);
+
+        required_align_offset != usize::MAX
+            && !self.needs_grow(
+                mem::size_of_val(elem) + mem::size_of::<PtrData<T>>() + required_align_offset,
+            )
+    }
+
+    /// Finds the space that the element at `index` could occupy without touching other elements,
+    /// including the padding in front of it. Returns the start and end offsets of that space.
+    ///
+    /// As long as the elements are in order, their physical neighbours are their neighbours by index, so this is O(1)
+    /// unless those are zero sized. After the `PtrData` has been reordered, this has to look at every element,
+    /// which makes it O(n).
+    /// Zero sized elements are ignored, since they don't occupy any memory.
+    /// # Safety
+    /// `index` must not be out of bounds
+    pub unsafe fn free_space_around(&self, index: usize) -> (usize, usize) {
+        if self.in_order {
+            let sized_elem = |i| {
+                // SAFETY: The closure is only called with indices that are in bounds
+                let (offset, size) = unsafe { (self.get_data(i).offset, self.sizeof_elem(i)) };
+                (size != 0).then_some((offset, size))
+            };
+
+            let start = (0..index)
+                .rev()
+                .find_map(sized_elem)
+                .map_or(0, |(offset, size)| offset + size);
+            let end = (index + 1..self.len)
+                .find_map(sized_elem)
+                .map_or(self.cap - self.data_section_size(), |(offset, _)| offset);
+
+            return (start, end);
+        }
+
+        // SAFETY: We can rely on `index` not being out of bounds
+        let offset = unsafe { self.get_data(index).offset };
+
+        // this is where the free space, including padding, where we could place the element starts
+        // since there might be padding for the previous element, this is sometimes before `offset`
+        let mut start = 0;
+        // if there is no element after this one, it can grow into the free space in the middle
+        let mut end = self.cap - self.data_section_size();
+
+        for i in (0..self.len).filter(|&i| i != index) {
+            // SAFETY: `i` is in bounds
+            let (other_offset, other_size) =
+                unsafe { (self.get_data(i).offset, self.sizeof_elem(i)) };
+
+            if other_size == 0 {
+                continue;
+            }
+
+            if other_offset < offset {
+                start = cmp::max(start, other_offset + other_size);
+            } else {
+                end = cmp::min(end, other_offset);
+            }
+        }
+
+        (start, end)
+    }
+
+    /// Swaps the elements at `a` and `b` by swapping their `PtrData`
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let len = self.len;
+        self.check_index(a);
+        self.check_index(b);
+
+        self.data_section_mut().swap(len - 1 - a, len - 1 - b);
+        self.in_order &= a == b;
+    }
+
+    /// Reverses the order of the elements by reversing their `PtrData`
+    pub fn reverse(&mut self) {
+        self.data_section_mut().reverse();
+        self.in_order &= self.len <= 1;
+    }
+
+    /// Rotates the elements to the left by rotating their `PtrData`. Since the data section is stored
+    /// in reverse, this is a right rotation on the data section.
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(
+            mid <= self.len,
+            "Out of bounds, rotating by {} for len {}",
+            mid,
+            self.len
+        );
+
+        self.data_section_mut().rotate_right(mid);
+        self.in_order &= mid % self.len.max(1) == 0;
+    }
+
+    /// Rotates the elements to the right by rotating their `PtrData`. Since the data section is stored
+    /// in reverse, this is a left rotation on the data section.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(
+            k <= self.len,
+            "Out of bounds, rotating by {} for len {}",
+            k,
+            self.len
+        );
+
+        self.data_section_mut().rotate_left(k);
+        self.in_order &= k % self.len.max(1) == 0;
+    }
+
+    /// Whether the elements are laid out in memory in the same order as their indices
+    pub fn is_physically_ordered(&self) -> bool {
+        (1..self.len).all(|i| {
+            // SAFETY: `i` and `i - 1` are in bounds
+            unsafe { self.get_data(i - 1).offset <= self.get_data(i).offset }
+        })
+    }
+
+    /// The offset of the element at `index` from the start of the buffer
+    /// # Safety
+    /// `index` must not be out of bounds
+    pub unsafe fn get_offset(&self, index: usize) -> usize {
+        // SAFETY: We can rely on `index` not being out of bounds
+        unsafe { self.get_data(index).offset }
+    }
+
+    /// The metadata of the element at `index`, without touching the element itself
+    /// # Safety
+    /// `index` must not be out of bounds
+    pub unsafe fn get_meta(&self, index: usize) -> <T as Pointee>::Metadata {
+        // SAFETY: We can rely on `index` not being out of bounds
+        unsafe { self.get_data(index).meta }
+    }
+
+    /// Overwrites the metadata of the element at `index`
+    /// # Safety
+    /// `index` must not be out of bounds, and the metadata must be valid for the element
+    pub unsafe fn set_meta(&mut self, index: usize, meta: <T as Pointee>::Metadata) {
+        // SAFETY: We can rely on `index` not being out of bounds
+        unsafe {
+            let offset = self.get_data(index).offset;
+            self.set_data(index, PtrData { offset, meta });
+        }
+    }
+
+    /// The `PtrData` section as a slice. It's stored in reverse, so the `PtrData` for index `0` is the last one
+    fn data_section_mut(&mut self) -> &mut [PtrData<T>] {
+        if self.len == 0 {
+            return &mut [];
+        }
+
+        // SAFETY: `self.len - 1` is in bounds
+        let data_ptr = unsafe { self.get_data_ptr(self.len - 1) };
+
+        // SAFETY: There are `self.len` initialized `PtrData<T>` from there until the end of the allocation.
+        //         The pointer is aligned, see `Self::get_data`
+        unsafe { slice::from_raw_parts_mut(data_ptr, self.len) }
+    }
+
+    /// The bytes needed for `elements` more elements with a combined size of `element_bytes`,
+    /// including their metadata and the worst case padding, rounded up to the alignment of the metadata
+    pub fn capacity_for(elements: usize, element_bytes: usize) -> usize {
+        let padding = match <T as KnownAlign>::known_align() {
+            // all elements have the same alignment and their sizes are multiples of it,
+            // so only the first one might need padding
+            Some(align) => align - 1,
+            // the alignment of an element is at most its size, unless it's zero sized.
+            // Over aligned zero sized elements aren't covered, their alignment isn't known here
+            None => element_bytes,
+        };
+
+        elements
+            .checked_mul(mem::size_of::<PtrData<T>>())
+            .and_then(|metadata| metadata.checked_add(element_bytes))
+            .and_then(|bytes| bytes.checked_add(padding))
+            .and_then(|bytes| bytes.checked_add(Self::data_align() - 1))
+            .map(|bytes| force_align(bytes, Self::data_align()))
+            .expect("capacity overflow")
+    }
+
+    /// Overwrites the `PtrData<T>` at `index`
+    /// # Safety
+    /// `index` must be in bounds, or only so much out of bounds as to not overwrite element data
+    unsafe fn set_data(&mut self, index: usize, data: PtrData<T>) {
+        // SAFETY: We can assume that `get_data_ptr` returns valid pointers to `PtrData<T>`
+        unsafe { *self.get_data_ptr(index) = data };
+    }
+
+    /// # Safety
+    /// `index` must not be out of bounds
+    pub unsafe fn sizeof_elem(&self, index: usize) -> usize {
+        // SAFETY: We can rely on `index` not being out of bounds
+        let data = unsafe { self.get_data(index) };
+
+        // SAFETY: We can assume that the `offset` from `data` is not out of bounds
+        let elem_ptr = unsafe { self.ptr.as_ptr().add(data.offset) };
+
+        // allocate a new `Box` for the return value
+        let elem_fat_ptr = ptr::from_raw_parts_mut::<T>(elem_ptr as *mut (), data.meta);
+        // SAFETY: The metadata has been preserved, and the pointer has been properly aligned and initialized
+        // when the element was added
+        let elem_fat_ref = unsafe { &*elem_fat_ptr };
+
+        mem::size_of_val(elem_fat_ref)
+    }
+
+    const fn data_align() -> usize {
+        mem::align_of::<PtrData<T>>()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = force_align(capacity, Self::data_align());
+
+        let mut vechonk = Self::new();
+
+        if capacity == 0 {
+            return vechonk;
+        }
+
+        vechonk.reset_alloc(NonZeroUsize::new(capacity).unwrap());
+        vechonk
+    }
+
+    pub fn push(&mut self, element: Box<T>) {
+        let elem_ptr = Box::into_raw(element);
+
+        // SAFETY: `elem_ptr` comes from a `Box<T>`, and the value is not used after it has been copied
+        unsafe { self.push_raw(elem_ptr) };
+
         // SAFETY: `elem_ptr` comes from a `Box<T>`
         unsafe {
             dealloc_box(elem_ptr);
         }
     }
 
+    /// Copies the element behind `elem_ptr` to the end of the `RawVechonk`, growing it if needed
+    /// # Safety
+    /// `elem_ptr` must point to a valid `T` that doesn't live inside this `RawVechonk`.
+    /// The value is moved into the `RawVechonk`, so it must not be used or dropped afterwards
+    pub unsafe fn push_raw(&mut self, elem_ptr: *const T) {
+        // SAFETY: We can assume that `elem_ptr` points to a valid `T` that doesn't live inside this `RawVechonk`
+        while !unsafe { self.try_push_raw(elem_ptr) } {
+            // SAFETY: We can assume that `elem_ptr` points to a valid `T`
+            let elem_size = mem::size_of_val(unsafe { &*elem_ptr });
+            let elem_align = mem::align_of_val(unsafe { &*elem_ptr });
+
+            // Regrowing lays out all elements again in a new allocation, so both the end of the elements
+            // and the padding needed for this element might be different afterwards, which is why we try again
+            self.regrow(elem_size + mem::size_of::<PtrData<T>>() + elem_align - 1);
+        }
+    }
+
     /// Insert an element at an index.
     /// * If the insertion was successful, the old element is returned.
     /// * If the new element doesn't fit the gap or can't be aligned, it is returned.
@@ -141,24 +495,9 @@ impl<T: ?Sized> RawVechonk<T> {
         │1|2│0      │3875│
          */
 
-        // this is where the free space, including padding, where we could place the element starts
-        // since there might be padding for the previous element, this is sometimes before `elem_offset`
-        let free_space_start_offset = if index == 0 {
-            0
-        } else {
-            // SAFETY: `index` is not 0
-            unsafe {
-                let data_element_before = self.get_data(index - 1);
-                data_element_before.offset + self.sizeof_elem(index - 1)
-            }
-        };
-
-        let next_element_start_offset = if index == self.len - 1 {
-            self.cap - self.data_section_size()
-        } else {
-            // SAFETY: We have checked that `index` is not the last element
-            unsafe { self.get_data(index + 1).offset }
-        };
+        // SAFETY: We have checked that `index` is in bounds
+        let (free_space_start_offset, next_element_start_offset) =
+            unsafe { self.free_space_around(index) };
 
         let elem_size = mem::size_of_val::<T>(element.as_ref());
         let elem_align = mem::align_of_val::<T>(element.as_ref());
@@ -175,10 +514,10 @@ impl<T: ?Sized> RawVechonk<T> {
         }
 
         let new_elem_starting_offset = free_space_start_offset + required_align_offset;
-        let actual_free_space = next_element_start_offset.saturating_sub(new_elem_starting_offset);
 
-        if actual_free_space < elem_size {
-            // We don't have enough space for the element.
+        if new_elem_starting_offset + elem_size > next_element_start_offset {
+            // We don't have enough space for the element. This also applies to zero sized elements,
+            // which could otherwise end up behind the gap if it's too small to align them.
             return Err(element);
         }
 
@@ -203,6 +542,9 @@ impl<T: ?Sized> RawVechonk<T> {
         // SAFETY: `elem_ptr` comes from the box
         unsafe { dealloc_box(elem_ptr) };
 
+        // the element might have grown into the free space at the end of the element section
+        self.elem_size = cmp::max(self.elem_size, new_elem_starting_offset + elem_size);
+
         Ok(old_elem)
     }
 
@@ -211,12 +553,11 @@ impl<T: ?Sized> RawVechonk<T> {
             return None;
         }
 
-        // SAFETY: `self.len` cannot be 0 and must therefore be in bounds, we decrement the len below
+        // SAFETY: `self.len` cannot be 0 and must therefore be in bounds, we remove the element below
         let boxed = unsafe { self.box_elem_unchecked(self.len - 1) };
 
-        // We don't need to care about our memory, we can just decrement the `len` and let the old memory be, it's
-        // now semantically uninitialized
-        self.len -= 1;
+        // SAFETY: `self.len` cannot be 0, and the element has been moved into the box
+        unsafe { self.remove_last() };
 
         Some(boxed)
     }
@@ -274,99 +615,514 @@ impl<T: ?Sized> RawVechonk<T> {
         unsafe { Box::from_raw(box_fat_ptr) }
     }
 
-    /// Get a raw ptr to an element. Be careful about casting this into a `mut &T`
-    /// # SAFETY
-    /// The index must be in bounds
-    pub unsafe fn get_unchecked_ptr(&self, index: usize) -> *mut T {
-        // SAFETY: We can assume that `index` is valid
-        let data = unsafe { self.get_data(index) };
+    /// Reorders the `PtrData` such that the element at index `i` is the one that was at index `permutation[i]`
+    pub fn permute(&mut self, permutation: &[usize]) {
+        let len = self.len;
+
+        assert_eq!(
+            permutation.len(),
+            len,
+            "Permutation has length {} for len {}",
+            permutation.len(),
+            len
+        );
+
+        // validate everything first, so that we never end up with two `PtrData` pointing to the same element
+        let mut seen = vec![false; len];
+        for &from in permutation {
+            assert!(
+                from < len && !mem::replace(&mut seen[from], true),
+                "Not a permutation, index {} is out of bounds or appears twice",
+                from
+            );
+        }
 
-        let elem_ptr = unsafe { self.ptr.as_ptr().add(data.offset) };
+        let data = self.data_section_mut();
+        let old_data = data.to_vec();
 
-        ptr::from_raw_parts_mut(elem_ptr as *mut (), data.meta)
+        for (to, &from) in permutation.iter().enumerate() {
+            data[len - 1 - to] = old_data[len - 1 - from];
+        }
+
+        self.in_order &= permutation.iter().enumerate().all(|(to, &from)| to == from);
     }
 
-    fn regrow(&mut self, min_size: NonZeroUsize) {
-        // We just create a new one and copy all elements over.
-        // This is because it's almost impossible to copy around the alignment properly,
-        // as we need to dynamically align each element, and the alignment of our allocation
-        // might have decreased after the realloc.
+    /// Moves the elements from `at` onwards into a new `RawVechonk`, keeping their order
+    pub fn split_off(&mut self, at: usize) -> RawVechonk<T> {
+        assert!(
+            at <= self.len,
+            "Out of bounds, splitting at {} for len {}",
+            at,
+            self.len
+        );
+
+        // enough space for all elements even if they need the worst case padding, so that we never regrow
+        let capacity = (at..self.len)
+            .map(|i| {
+                // SAFETY: `i` is in bounds
+                let elem = unsafe { &*self.get_unchecked_ptr(i) };
+                mem::size_of_val(elem) + mem::align_of_val(elem) - 1 + mem::size_of::<PtrData<T>>()
+            })
+            .sum::<usize>();
+
+        let mut tail = RawVechonk::with_capacity(force_align(
+            capacity + Self::data_align() - 1,
+            Self::data_align(),
+        ));
+        tail.growth = self.growth;
+
+        for i in at..self.len {
+            // SAFETY: `i` is in bounds, and we shrink `self.len` below so that the element is moved
+            unsafe { tail.push_raw(self.get_unchecked_ptr(i)) };
+        }
 
-        let new_cap = force_align(min_size.get() * 2, Self::data_align());
+        self.len = at;
 
-        let new = RawVechonk::with_capacity(new_cap);
-        let old = mem::replace(self, new);
-        crate::IntoIter::from_raw(old).for_each(|e| {
-            self.push(e);
-        });
+        // The elements we just moved out might not have been at the end of the element section after reordering,
+        // so we can only reclaim the memory behind the last remaining element
+        self.elem_size = (0..self.len)
+            .map(|i| {
+                // SAFETY: `i` is in bounds
+                unsafe { self.get_data(i).offset + self.sizeof_elem(i) }
+            })
+            .max()
+            .unwrap_or(0);
+
+        tail
     }
 
-    /// Allocates the `Vechonk`, setting its capacity to `size`.
-    fn reset_alloc(&mut self, size: NonZeroUsize) {
-        let layout = Layout::from_size_align(size.get(), Self::data_align()).unwrap();
+    /// Moves all elements of `other` to the end, leaving `other` empty.
+    ///
+    /// The element section of `other` is copied over in one go, and then the offsets in the `PtrData` are
+    /// adjusted. To keep every element aligned, the copy is placed at an address with the same
+    /// offset to the largest alignment of the elements as in `other`.
+    pub fn append(&mut self, other: &mut RawVechonk<T>) {
+        if other.len == 0 {
+            return;
+        }
 
-        // SAFETY: layout is guaranteed to have a non-zero size
-        let alloced_ptr = unsafe { alloc::alloc::alloc(layout) };
+        let mut max_align = 1;
+        // the end of the used part of `other`, zero sized elements might point behind `other.elem_size`
+        let mut other_extent = other.elem_size;
 
-        self.ptr =
-            NonNull::new(alloced_ptr).unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout));
+        for i in 0..other.len {
+            // SAFETY: `i` is in bounds
+            let (offset, elem) =
+                unsafe { (other.get_data(i).offset, &*other.get_unchecked_ptr(i)) };
 
-        self.cap = size.get();
+            max_align = cmp::max(max_align, mem::align_of_val(elem));
+            other_extent = cmp::max(other_extent, offset + mem::size_of_val(elem));
+        }
+
+        let data_size = mem::size_of::<PtrData<T>>() * (self.len + other.len);
+
+        let dest_offset = loop {
+            // the copy has to start at the same position relative to `max_align` as the elements in `other`
+            let shift = other
+                .ptr
+                .as_ptr()
+                .addr()
+                .wrapping_sub(self.ptr.as_ptr().addr())
+                % max_align;
+            let dest_offset =
+                self.elem_size + (shift + max_align - self.elem_size % max_align) % max_align;
+
+            if dest_offset + other_extent + data_size <= self.cap {
+                break dest_offset;
+            }
+
+            // the padding might change after moving, so we have to check again
+            let required = max_align + other_extent + (data_size - self.data_section_size());
+            self.regrow(required);
+        };
+
+        // SAFETY: We have checked above that there's enough space behind `dest_offset`.
+        //         The allocations can't overlap, since both own their memory
+        unsafe {
+            ptr::copy_nonoverlapping(
+                other.ptr.as_ptr(),
+                self.ptr.as_ptr().add(dest_offset),
+                other.elem_size,
+            )
+        };
+
+        for i in 0..other.len {
+            // SAFETY: `i` is in bounds for `other`, `self.len + i` is still in the free space in the middle,
+            //         since we have checked that there's enough space for all `PtrData` above
+            unsafe {
+                let data = other.get_data(i);
+                let elem_ptr = other.get_unchecked_ptr(i);
+                self.write_meta_data(elem_ptr, dest_offset + data.offset, self.len + i);
+            }
+        }
+
+        self.len += other.len;
+        self.elem_size = dest_offset + other_extent;
+        // the elements of `other` are all behind the ones of `self`
+        self.in_order &= other.in_order;
+
+        // the elements have been moved, so `other` must not touch them again
+        other.len = 0;
+        other.elem_size = 0;
+        other.in_order = true;
     }
 
-    /// Writes the metadata of the `ptr` and the `offset` to a `PtrData<T>` at `index`
-    /// # Safety
-    /// `index` must be in bounds, or only so much out of bounds as to not overwrite element data
-    unsafe fn write_meta_data(&mut self, ptr: *mut T, offset: usize, index: usize) {
-        // SAFETY: `index` is not out of bounds, and we are overwriting the element afterwards
-        let data_ptr = unsafe { self.get_data_ptr(index) };
+    /// Removes consecutive elements for which `same_bucket` returns true, dropping them in place.
+    /// `same_bucket` is called with the element and the previous remaining one.
+    /// Afterwards, the remaining elements are compacted.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        if self.len <= 1 {
+            return;
+        }
 
-        let meta = ptr::metadata::<T>(ptr);
+        /// Moves the remaining `PtrData` to the front when dropped, even if `same_bucket` or a destructor panics
+        struct FillGapOnDrop<'a, T: ?Sized> {
+            raw: &'a mut RawVechonk<T>,
+            read: usize,
+            write: usize,
+        }
 
-        let new_data: PtrData<T> = PtrData { offset, meta };
+        impl<T: ?Sized> Drop for FillGapOnDrop<'_, T> {
+            fn drop(&mut self) {
+                let len = self.raw.len;
 
-        // SAFETY: We can assume that `get_data_ptr` returns valid pointers to `PtrData<T>`
-        unsafe { *data_ptr = new_data };
+                for i in self.read..len {
+                    // SAFETY: `i` is in bounds, and `self.write + i - self.read` is less than `i`
+                    unsafe {
+                        let data = self.raw.get_data(i);
+                        self.raw.set_data(self.write + i - self.read, data);
+                    }
+                }
+
+                self.raw.len = self.write + (len - self.read);
+            }
+        }
+
+        let mut gap = FillGapOnDrop {
+            raw: self,
+            read: 1,
+            write: 1,
+        };
+
+        while gap.read < gap.raw.len {
+            // SAFETY: Both indices are in bounds and `write - 1 < read`, so the elements are distinct.
+            //         Only zero sized elements can share their memory, and those can't alias.
+            let (current, previous) = unsafe {
+                (
+                    &mut *gap.raw.get_unchecked_ptr(gap.read),
+                    &mut *gap.raw.get_unchecked_ptr(gap.write - 1),
+                )
+            };
+
+            if same_bucket(current, previous) {
+                // increment first, so that the element isn't touched again if its destructor panics
+                gap.read += 1;
+
+                // SAFETY: The `PtrData` of the element gets overwritten or cut off, so it can't be used again
+                unsafe { ptr::drop_in_place(current) };
+            } else {
+                // SAFETY: Both indices are in bounds
+                unsafe {
+                    let data = gap.raw.get_data(gap.read);
+                    gap.raw.set_data(gap.write, data);
+                }
+
+                gap.read += 1;
+                gap.write += 1;
+            }
+        }
+
+        drop(gap);
+
+        self.compact();
     }
 
-    /// Get the data for the index
-    /// # Safety
-    /// `index` must not be out of bounds
-    unsafe fn get_data(&self, index: usize) -> PtrData<T> {
-        // SAFETY: We can assume that `index` is not out of bounds
-        let data_ptr = unsafe { self.get_data_ptr(index) };
+    /// Moves all elements towards the front of the allocation, in the order they are laid out in memory,
+    /// so that there are no gaps between them except for the padding required by their alignment.
+    /// Returns the amount of bytes that were reclaimed.
+    ///
+    /// Zero sized elements are never moved, since they don't occupy any memory
+    pub fn compact(&mut self) -> usize {
+        let mut order = (0..self.len).collect::<Vec<_>>();
+
+        if !self.is_physically_ordered() {
+            // SAFETY: All indices are in bounds
+            order.sort_by_key(|&i| unsafe { self.get_data(i).offset });
+        }
 
-        // SAFETY: The pointer is aligned because `self.ptr` is aligned and `data_offset` is a multiple of the alignment
-        //         The value behind it is always a `PtrData<T>`
-        unsafe { *data_ptr }
+        let mut cursor = 0;
+        // the elements keep their physical order, but we can find out whether it matches their indices on the way
+        let mut in_order = true;
+        let mut previous_index = None;
+
+        for i in order {
+            // SAFETY: `i` is in bounds
+            let (data, elem) = unsafe { (self.get_data(i), &*self.get_unchecked_ptr(i)) };
+            let (elem_size, elem_align) = (mem::size_of_val(elem), mem::align_of_val(elem));
+
+            if elem_size == 0 {
+                continue;
+            }
+
+            in_order &= previous_index.map_or(true, |previous| previous < i);
+            previous_index = Some(i);
+
+            // SAFETY: `cursor` is never behind the start of the current element
+            let dest_offset =
+                cursor + unsafe { self.ptr.as_ptr().add(cursor).align_offset(elem_align) };
+
+            // Since we go through the elements in the order they are laid out in memory, everything before `cursor`
+            // has already been moved, and `dest_offset` can't be behind the current element, as its start is aligned.
+            if dest_offset != data.offset {
+                // SAFETY: Both ranges are in bounds, see above, and `ptr::copy` can deal with the overlap
+                unsafe {
+                    ptr::copy(
+                        self.ptr.as_ptr().add(data.offset),
+                        self.ptr.as_ptr().add(dest_offset),
+                        elem_size,
+                    );
+                    self.set_data(
+                        i,
+                        PtrData {
+                            offset: dest_offset,
+                            meta: data.meta,
+                        },
+                    );
+                }
+            }
+
+            cursor = dest_offset + elem_size;
+        }
+
+        let reclaimed = self.elem_size.saturating_sub(cursor);
+        self.elem_size = cursor;
+        self.in_order = in_order;
+
+        reclaimed
     }
 
+    /// Makes sure that there are at least `new_size` bytes of space for the element at `index`.
+    /// If the space behind it is taken, it is moved to the end of the element section, regrowing if needed.
+    /// Returns the offset of the element, which might have changed.
+    ///
+    /// The element at the end of the element section grows in O(1), unless it regrows. Other elements need to look
+    /// at the free space around them, see `Self::free_space_around`
     /// # Safety
     /// `index` must not be out of bounds
-    unsafe fn sizeof_elem(&self, index: usize) -> usize {
+    pub unsafe fn reserve_elem(&mut self, index: usize, new_size: usize) -> usize {
         // SAFETY: We can rely on `index` not being out of bounds
-        let data = unsafe { self.get_data(index) };
+        let (data, elem) = unsafe { (self.get_data(index), &*self.get_unchecked_ptr(index)) };
+        let (elem_size, elem_align) = (mem::size_of_val(elem), mem::align_of_val(elem));
 
-        // SAFETY: We can assume that the `offset` from `data` is not out of bounds
-        let elem_ptr = unsafe { self.ptr.as_ptr().add(data.offset) };
+        if new_size <= elem_size {
+            return data.offset;
+        }
 
-        // allocate a new `Box` for the return value
-        let elem_fat_ptr = ptr::from_raw_parts_mut::<T>(elem_ptr as *mut (), data.meta);
-        // SAFETY: The metadata has been preserved, and the pointer has been properly aligned and initialized
-        // when the element was added
-        let elem_fat_ref = unsafe { &*elem_fat_ptr };
+        // Nothing comes after the element at the end of the element section, so it can grow into the free space in
+        // the middle. Zero sized elements might have been left there by other elements, so they have to be the last
+        // one by index, to not be laid out behind elements that come after them.
+        if data.offset + elem_size == self.elem_size && (elem_size != 0 || index == self.len - 1) {
+            if !self.needs_grow(new_size - elem_size) {
+                self.elem_size = data.offset + new_size;
+                return data.offset;
+            }
 
-        mem::size_of_val(elem_fat_ref)
+            self.regrow(new_size + elem_align - 1);
+
+            // the element might not be at the end anymore, since the elements have been laid out in order again
+            // SAFETY: `index` is still in bounds
+            return unsafe { self.reserve_elem(index, new_size) };
+        }
+
+        // SAFETY: We can rely on `index` not being out of bounds
+        let (free_space_start, free_space_end) = unsafe { self.free_space_around(index) };
+
+        // zero sized elements might be inside of other elements, so we have to check the start as well
+        if free_space_start <= data.offset && data.offset + new_size <= free_space_end {
+            // the element might grow into the free space at the end of the element section
+            self.elem_size = cmp::max(self.elem_size, data.offset + new_size);
+            return data.offset;
+        }
+
+        if self.needs_grow(new_size + elem_align - 1) {
+            self.regrow(new_size + elem_align - 1);
+
+            // the elements have been moved, so the element might fit where it is now
+            // SAFETY: `index` is still in bounds
+            return unsafe { self.reserve_elem(index, new_size) };
+        }
+
+        // SAFETY: `self.elem_size` is in bounds, see `Self::push_raw`
+        let dest_offset = self.elem_size
+            + unsafe {
+                self.ptr
+                    .as_ptr()
+                    .add(self.elem_size)
+                    .align_offset(elem_align)
+            };
+
+        // SAFETY: The element is behind `self.elem_size` and the destination is after it, so they don't overlap.
+        //         We have checked that there's enough space at the destination above
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.ptr.as_ptr().add(data.offset),
+                self.ptr.as_ptr().add(dest_offset),
+                elem_size,
+            );
+            self.set_data(
+                index,
+                PtrData {
+                    offset: dest_offset,
+                    meta: data.meta,
+                },
+            );
+        }
+
+        self.elem_size = dest_offset + new_size;
+        // the element is now behind all others, which is only in order if it's the last one
+        self.in_order &= index == self.len - 1;
+
+        dest_offset
     }
 
-    /// # Safety
-    /// `index` must be in bounds, or only so much out of bounds to not overwrite element data
-    unsafe fn get_data_ptr(&self, index: usize) -> *mut PtrData<T> {
-        let data_offset = self.offset_for_data(index);
+    /// Makes sure that there are at least `additional` free bytes, regrowing if needed
+    pub fn reserve(&mut self, additional: usize) {
+        // the padding of the elements might change when they are moved, so this can take more than one regrow
+        while self.needs_grow(additional) {
+            self.regrow(additional);
+        }
+    }
 
-        // SAFETY: The offset will always be less than `self.cap`, because we can't have more than `self.len` `PtrData`
-        let data_ptr = unsafe { self.ptr.as_ptr().add(data_offset) };
-        data_ptr as *mut PtrData<T>
+    /// Moves everything into a bigger allocation, that has room for `additional` more bytes
+    /// than are used now. The growth strategy decides how much bigger it actually gets
+    fn regrow(&mut self, additional: usize) {
+        // We just create a new one and copy all elements over.
+        // This is because it's almost impossible to copy around the alignment properly,
+        // as we need to dynamically align each element, and the alignment of our allocation
+        // might have decreased after the realloc.
+
+        let required = self.elem_size + self.data_section_size() + additional;
+        let new_cap = cmp::max(self.growth.grow(self.cap, required), required);
+
+        // round up, so that we never get less than required
+        let new_cap = force_align(new_cap + Self::data_align() - 1, Self::data_align());
+
+        self.relocate(new_cap);
+    }
+
+    /// Moves all elements into a new allocation of `capacity` bytes, laying them out
+    /// contiguously in their logical order
+    pub fn relocate(&mut self, capacity: usize) {
+        let mut new = RawVechonk::with_capacity(capacity);
+        new.growth = self.growth;
+        let old = mem::replace(self, new);
+
+        for i in 0..old.len {
+            // SAFETY: `i` is in bounds, and `old` is deallocated below without touching the elements again,
+            //         so the elements are moved
+            unsafe { self.push_raw(old.get_unchecked_ptr(i)) };
+        }
+
+        // SAFETY: `old` owned the allocation, and it has the length `old.cap`
+        unsafe { Self::dealloc(old.cap, old.ptr.as_ptr()) };
+    }
+
+    /// Moves all elements into a new allocation of at least `capacity` bytes, laying them out contiguously
+    /// in `physical_order`, while keeping their indices. The allocation is made bigger if they don't fit
+    ///
+    /// `physical_order` must be a permutation of `0..self.len`
+    pub fn relocate_in_order(&mut self, capacity: usize, physical_order: &[usize]) {
+        debug_assert_eq!(physical_order.len(), self.len);
+
+        let mut capacity = capacity;
+
+        let new = loop {
+            let new = RawVechonk::<T>::with_capacity(capacity);
+
+            // the padding depends on the address of the new allocation, so we can only check after allocating
+            let elem_size = physical_order
+                .iter()
+                // SAFETY: All indices are in bounds
+                .map(|&i| unsafe { &*self.get_unchecked_ptr(i) })
+                .fold(0, |cursor, elem| {
+                    cursor
+                        + new
+                            .ptr
+                            .as_ptr()
+                            .wrapping_add(cursor)
+                            .align_offset(mem::align_of_val(elem))
+                        + mem::size_of_val(elem)
+                });
+
+            let required = elem_size + self.data_section_size();
+
+            if required <= new.cap {
+                break new;
+            }
+
+            // SAFETY: `new` owns its allocation, and it has the length `new.cap`
+            unsafe { Self::dealloc(new.cap, new.ptr.as_ptr()) };
+
+            // leave room for the worst case alignment of the new allocation
+            let max_align = (0..self.len)
+                // SAFETY: All indices are in bounds
+                .map(|i| mem::align_of_val(unsafe { &*self.get_unchecked_ptr(i) }))
+                .max()
+                .unwrap_or(1);
+            capacity = required + max_align + Self::data_align();
+        };
+
+        let old = mem::replace(self, new);
+        self.growth = old.growth;
+        let mut cursor = 0;
+
+        for &i in physical_order {
+            // SAFETY: `i` is in bounds
+            let elem_ptr = unsafe { old.get_unchecked_ptr(i) };
+            // SAFETY: `elem_ptr` points to a valid element
+            let (elem_size, elem_align) =
+                unsafe { (mem::size_of_val(&*elem_ptr), mem::align_of_val(&*elem_ptr)) };
+
+            // SAFETY: We have checked above that all elements fit, so `cursor` is in bounds
+            let dest_offset =
+                cursor + unsafe { self.ptr.as_ptr().add(cursor).align_offset(elem_align) };
+
+            // SAFETY: The allocations can't overlap, and we have checked above that there is enough space.
+            //         The element is moved, since `old` is deallocated below without touching the elements again
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    elem_ptr as *const u8,
+                    self.ptr.as_ptr().add(dest_offset),
+                    elem_size,
+                );
+                self.write_meta_data(elem_ptr, dest_offset, i);
+            }
+
+            cursor = dest_offset + elem_size;
+        }
+
+        self.len = old.len;
+        self.elem_size = cursor;
+        self.in_order = physical_order.windows(2).all(|pair| pair[0] < pair[1]);
+
+        // SAFETY: `old` owned the allocation, and it has the length `old.cap`
+        unsafe { Self::dealloc(old.cap, old.ptr.as_ptr()) };
+    }
+
+    /// Allocates the `Vechonk`, setting its capacity to `size`.
+    fn reset_alloc(&mut self, size: NonZeroUsize) {
+        let layout = Layout::from_size_align(size.get(), Self::data_align()).unwrap();
+
+        // SAFETY: layout is guaranteed to have a non-zero size
+        let alloced_ptr = unsafe { alloc::alloc::alloc(layout) };
+
+        self.ptr =
+            NonNull::new(alloced_ptr).unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout));
+
+        self.cap = size.get();
     }
 
     /// SAFETY: The allocation must be owned by `ptr` and have the length `cap`
@@ -381,30 +1137,13 @@ impl<T: ?Sized> RawVechonk<T> {
 
         unsafe { alloc::alloc::dealloc(ptr, layout) };
     }
-
-    /// Returns a multiple of the alignment of `PtrData<T>`, since `self.cap` is one, and so is the size
-    const fn offset_for_data(&self, index: usize) -> usize {
-        self.cap
-            .saturating_sub(mem::size_of::<PtrData<T>>() * (index + 1))
-    }
-
-    fn needs_grow(&self, additional_size: usize) -> bool {
-        additional_size > self.cap - (self.elem_size + self.data_section_size())
-    }
-
-    pub const fn data_section_size(&self) -> usize {
-        self.len * mem::size_of::<PtrData<T>>()
-    }
-
-    const fn data_align() -> usize {
-        mem::align_of::<PtrData<T>>()
-    }
 }
 
 /// Deallocates memory from a `Box<T>`
 /// # Safety
 /// `ptr` must point to an allocation from a `Box<T>`, and must be safe to free
-unsafe fn dealloc_box<T: ?Sized>(ptr: *mut T) {
+#[cfg(feature = "alloc")]
+pub unsafe fn dealloc_box<T: ?Sized>(ptr: *mut T) {
     // SAFETY: This was allocated by `Box`, so we know that it is valid.
     //         The ownership of the value was transferred to `Vechonk` by copying it out
 
@@ -0,0 +1,297 @@
+//! Parallel iterators for [`Vechonk`], behind the `rayon` feature
+//!
+//! Elements can be accessed by index in O(1), so the iterators are split in halves by index,
+//! and implement [`IndexedParallelIterator`].
+
+use crate::raw::RawVechonk;
+use crate::{Iter, IterMut, Vechonk};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+
+/// A parallel iterator over the elements of a [`Vechonk`], created by `par_iter`
+pub struct ParIter<'a, T: ?Sized> {
+    chonk: &'a Vechonk<T>,
+}
+
+impl<'a, T: ?Sized + Sync> IntoParallelIterator for &'a Vechonk<T> {
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter { chonk: self }
+    }
+}
+
+impl<'a, T: ?Sized + Sync> ParallelIterator for ParIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.chonk.len())
+    }
+}
+
+impl<T: ?Sized + Sync> IndexedParallelIterator for ParIter<'_, T> {
+    fn len(&self) -> usize {
+        self.chonk.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(IterProducer(self.chonk.iter()))
+    }
+}
+
+struct IterProducer<'a, T: ?Sized>(Iter<'a, T>);
+
+// SAFETY: The producer only hands out shared references, which can be sent if `T` is `Sync`
+unsafe impl<T: ?Sized + Sync> Send for IterProducer<'_, T> {}
+
+impl<'a, T: ?Sized + Sync> Producer for IterProducer<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(index);
+        (Self(left), Self(right))
+    }
+}
+
+/// A parallel iterator over the elements of a [`Vechonk`], created by `par_iter_mut`
+pub struct ParIterMut<'a, T: ?Sized> {
+    chonk: &'a mut Vechonk<T>,
+}
+
+impl<'a, T: ?Sized + Send> IntoParallelIterator for &'a mut Vechonk<T> {
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut { chonk: self }
+    }
+}
+
+impl<'a, T: ?Sized + Send> ParallelIterator for ParIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.chonk.len())
+    }
+}
+
+impl<T: ?Sized + Send> IndexedParallelIterator for ParIterMut<'_, T> {
+    fn len(&self) -> usize {
+        self.chonk.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(IterMutProducer(self.chonk.iter_mut()))
+    }
+}
+
+struct IterMutProducer<'a, T: ?Sized>(IterMut<'a, T>);
+
+// SAFETY: The halves never overlap, so every mutable reference is handed out exactly once
+unsafe impl<T: ?Sized + Send> Send for IterMutProducer<'_, T> {}
+
+impl<'a, T: ?Sized + Send> Producer for IterMutProducer<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(index);
+        (Self(left), Self(right))
+    }
+}
+
+/// A parallel iterator over the elements of a [`Vechonk`], created by `into_par_iter`
+pub struct IntoParIter<T: ?Sized> {
+    chonk: Vechonk<T>,
+}
+
+impl<T: ?Sized + Send> IntoParallelIterator for Vechonk<T> {
+    type Iter = IntoParIter<T>;
+    type Item = Box<T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIter { chonk: self }
+    }
+}
+
+impl<T: ?Sized + Send> ParallelIterator for IntoParIter<T> {
+    type Item = Box<T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.chonk.len())
+    }
+}
+
+impl<T: ?Sized + Send> IndexedParallelIterator for IntoParIter<T> {
+    fn len(&self) -> usize {
+        self.chonk.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        // We don't want to free the memory yet, it's freed by the guard once all elements have been moved out
+        let chonk = ManuallyDrop::new(self.chonk);
+        let guard = DeallocOnDrop(chonk.raw.copy());
+
+        callback.callback(BoxProducer {
+            raw: guard.0.copy(),
+            current_index: 0,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Frees the buffer of a `Vechonk` without touching the elements
+struct DeallocOnDrop<T: ?Sized>(RawVechonk<T>);
+
+impl<T: ?Sized> Drop for DeallocOnDrop<T> {
+    fn drop(&mut self) {
+        // SAFETY: We own the buffer, and it has the length `self.0.cap`
+        unsafe {
+            RawVechonk::<T>::dealloc(self.0.cap, self.0.ptr.as_ptr());
+        }
+    }
+}
+
+/// Moves the elements of a range out of the buffer into boxes. The range ends at `raw.len`
+struct BoxProducer<'a, T: ?Sized> {
+    raw: RawVechonk<T>,
+    current_index: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: The ranges never overlap, so every element is moved out exactly once
+unsafe impl<T: ?Sized + Send> Send for BoxProducer<'_, T> {}
+
+impl<T: ?Sized + Send> Producer for BoxProducer<'_, T> {
+    type Item = Box<T>;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.current_index + index;
+
+        let mut left_raw = self.raw.copy();
+        left_raw.len = mid;
+
+        let left = Self {
+            raw: left_raw,
+            current_index: self.current_index,
+            _marker: PhantomData,
+        };
+        let right = Self {
+            raw: self.raw,
+            current_index: mid,
+            _marker: PhantomData,
+        };
+
+        (left, right)
+    }
+}
+
+impl<T: ?Sized> Iterator for BoxProducer<'_, T> {
+    type Item = Box<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_index == self.raw.len {
+            return None;
+        }
+
+        // SAFETY: We just did a bounds check above
+        //         We also increment the `current_index`, to make sure that we never access it again
+        let elem = unsafe { self.raw.box_elem_unchecked(self.current_index) };
+
+        self.current_index += 1;
+
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.raw.len - self.current_index;
+
+        (count, Some(count))
+    }
+}
+
+impl<T: ?Sized> DoubleEndedIterator for BoxProducer<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_index == self.raw.len {
+            return None;
+        }
+
+        self.raw.len -= 1;
+
+        // SAFETY: We just did a bounds check above
+        //         We also decrement the length, to make sure that we never access it again
+        Some(unsafe { self.raw.box_elem_unchecked(self.raw.len) })
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for BoxProducer<'_, T> {}
+
+impl<T: ?Sized + Send> FromParallelIterator<Box<T>> for Vechonk<T> {
+    /// The elements are collected in parallel, and then moved into the `Vechonk` in order
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = Box<T>>,
+    {
+        let elements = par_iter.into_par_iter().collect::<Vec<_>>();
+
+        let mut chonk = Vechonk::new();
+        for element in elements {
+            chonk.push(element);
+        }
+        chonk
+    }
+}
@@ -0,0 +1,229 @@
+//! `Serialize` and `Deserialize` implementations, behind the `serde` feature
+//!
+//! A [`Vechonk`] is serialized as a sequence of its elements. Deserializing needs a bit of help, since
+//! unsized elements can't be deserialized on their own, see [`DeserializeElem`].
+
+use crate::{ElemMut, Vechonk};
+use core::fmt;
+use core::marker::PhantomData;
+use core::{mem, str};
+use serde::de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde::Deserialize;
+
+impl<T> Serialize for Vechonk<T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// An element type that can be deserialized into a [`Vechonk`]
+///
+/// This is implemented for `str` and `[T]`, which are written straight into the buffer of the `Vechonk`.
+///
+/// For trait objects, implement this on `dyn Trait` to act as a registry, that decides which concrete type to
+/// deserialize, for example by deserializing a tagged enum and pushing its content.
+///
+/// ```
+/// use serde::Deserialize;
+/// use vechonk::{DeserializeElem, Vechonk};
+///
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// #[derive(Deserialize)]
+/// enum Registry {
+///     Square(Square),
+/// }
+///
+/// impl<'de> DeserializeElem<'de> for dyn Shape {
+///     fn deserialize_into<D>(deserializer: D, chonk: &mut Vechonk<Self>) -> Result<(), D::Error>
+///     where
+///         D: serde::Deserializer<'de>,
+///     {
+///         match Registry::deserialize(deserializer)? {
+///             Registry::Square(square) => chonk.push(Box::new(square)),
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let chonk: Vechonk<dyn Shape> = serde_json::from_str(r#"[{ "Square": 2.0 }]"#).unwrap();
+/// assert_eq!(chonk[0].area(), 4.0);
+/// ```
+pub trait DeserializeElem<'de> {
+    /// Deserialize a single element and push it onto the end of `chonk`
+    fn deserialize_into<D>(deserializer: D, chonk: &mut Vechonk<Self>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+impl<'de> DeserializeElem<'de> for str {
+    fn deserialize_into<D>(deserializer: D, chonk: &mut Vechonk<Self>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor { chonk })
+    }
+}
+
+impl<'de, T> DeserializeElem<'de> for [T]
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize_into<D>(deserializer: D, chonk: &mut Vechonk<Self>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SliceVisitor { chonk })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Vechonk<T>
+where
+    T: ?Sized + DeserializeElem<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(VechonkVisitor {
+            _marker: PhantomData,
+        })
+    }
+}
+
+struct VechonkVisitor<T: ?Sized> {
+    _marker: PhantomData<Vechonk<T>>,
+}
+
+impl<'de, T> Visitor<'de> for VechonkVisitor<T>
+where
+    T: ?Sized + DeserializeElem<'de>,
+{
+    type Value = Vechonk<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut chonk = Vechonk::new();
+
+        while seq
+            .next_element_seed(ElemSeed { chonk: &mut chonk })?
+            .is_some()
+        {}
+
+        Ok(chonk)
+    }
+}
+
+/// Deserializes the next element directly into the `Vechonk`
+struct ElemSeed<'a, T: ?Sized> {
+    chonk: &'a mut Vechonk<T>,
+}
+
+impl<'de, 'a, T> DeserializeSeed<'de> for ElemSeed<'a, T>
+where
+    T: ?Sized + DeserializeElem<'de>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_into(deserializer, self.chonk)
+    }
+}
+
+struct StrVisitor<'a> {
+    chonk: &'a mut Vechonk<str>,
+}
+
+impl<'de, 'a> Visitor<'de> for StrVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        // SAFETY: `str` has no destructor and doesn't own anything, so copying its bytes is fine
+        unsafe { self.chonk.raw.push_raw(v) };
+        self.chonk.debug_check_invariants();
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+struct SliceVisitor<'a, T> {
+    chonk: &'a mut Vechonk<[T]>,
+}
+
+impl<'de, 'a, T> Visitor<'de> for SliceVisitor<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // don't trust the size hint too much, it could come from malicious input
+        let hint = seq.size_hint().unwrap_or(0).min(4096);
+        self.chonk.reserve_for(1, mem::size_of::<T>() * hint);
+
+        let empty: &[T] = &[];
+        // SAFETY: An empty slice doesn't contain anything that could be used again
+        unsafe { self.chonk.raw.push_raw(empty) };
+        let index = self.chonk.len() - 1;
+
+        // SAFETY: We just pushed the row, so the index is in bounds
+        let mut row = unsafe { ElemMut::new(self.chonk, index) };
+
+        // the row is at the end of the buffer, so every item is written right behind the previous one
+        while let Some(item) = seq.next_element()? {
+            row.push(item);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+//! A `Vechonk` that stores small amounts of elements inline, and only allocates once they don't fit anymore
+
+use crate::access::{impl_access, RawAccess};
+use crate::force_align;
+use crate::raw::{PtrData, RawVechonk};
+use crate::Vechonk;
+use alloc::boxed::Box;
+use core::cmp;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ptr::NonNull;
+
+/// A [`Vechonk`] that keeps its elements and their metadata in an inline buffer of `BYTES` bytes, and spills
+/// them into a `Vechonk` on the heap with the same layout once they don't fit anymore
+///
+/// The elements are laid out relative to the start of the inline buffer, which is aligned to
+/// [`SmallVechonk::INLINE_ALIGN`], so they stay aligned when the `SmallVechonk` is moved. Elements that need
+/// a bigger alignment spill to the heap right away.
+///
+/// ```
+/// # use vechonk::SmallVechonk;
+/// let mut chonk = SmallVechonk::<str, 64>::new();
+///
+/// chonk.push("hello".into());
+/// chonk.push("uwu".into());
+/// assert!(!chonk.spilled());
+///
+/// chonk.push("a string that is too long to fit inline".into());
+/// assert!(chonk.spilled());
+/// assert_eq!(&chonk[1], "uwu");
+/// ```
+///
+/// note: it does not run destructors for now, just like `Vechonk`
+pub struct SmallVechonk<T: ?Sized, const BYTES: usize> {
+    storage: Storage<T, BYTES>,
+}
+
+enum Storage<T: ?Sized, const BYTES: usize> {
+    Inline {
+        buf: InlineBuf<BYTES>,
+        len: usize,
+        elem_size: usize,
+        /// See [`RawVechonk::in_order`], it has to be kept across calls just like the length
+        in_order: bool,
+        _marker: PhantomData<T>,
+    },
+    Heap(Vechonk<T>),
+}
+
+#[repr(C, align(16))]
+struct InlineBuf<const BYTES: usize>([MaybeUninit<u8>; BYTES]);
+
+impl<T: ?Sized, const BYTES: usize> SmallVechonk<T, BYTES> {
+    /// The alignment of the inline buffer. Elements with a bigger alignment are always stored on the heap
+    pub const INLINE_ALIGN: usize = mem::align_of::<InlineBuf<BYTES>>();
+
+    /// Create a new empty `SmallVechonk` that doesn't allocate anything
+    pub const fn new() -> Self {
+        Self {
+            storage: Storage::Inline {
+                buf: InlineBuf([MaybeUninit::uninit(); BYTES]),
+                len: 0,
+                elem_size: 0,
+                in_order: true,
+                _marker: PhantomData,
+            },
+        }
+    }
+
+    /// The amount of elements in the `SmallVechonk`, O(1)
+    pub const fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Heap(chonk) => chonk.len(),
+        }
+    }
+
+    /// Whether the elements have been moved to the heap. Once spilled, they stay there
+    pub const fn spilled(&self) -> bool {
+        matches!(self.storage, Storage::Heap(_))
+    }
+
+    /// Pushes a new element. If it doesn't fit inline, all elements are moved to the heap first
+    pub fn push(&mut self, element: Box<T>) {
+        if !self.spilled() && !self.fits_inline(&element) {
+            let additional = mem::size_of_val::<T>(&element)
+                + mem::align_of_val::<T>(&element)
+                + mem::size_of::<PtrData<T>>();
+            self.spill(additional);
+        }
+
+        // this only regrows once spilled, since the element fits inline otherwise
+        self.with_raw(|raw| raw.push(element));
+    }
+
+    /// Get the last element, returns `None` if the `SmallVechonk` is empty
+    pub fn pop(&mut self) -> Option<Box<T>> {
+        self.with_raw(RawVechonk::pop)
+    }
+
+    /// Replace an element at an index, see [`Vechonk::try_replace`].
+    /// While inline, elements that need a bigger alignment than [`SmallVechonk::INLINE_ALIGN`] are always returned
+    pub fn try_replace(&mut self, index: usize, element: Box<T>) -> Result<Box<T>, Box<T>> {
+        if !self.spilled() && mem::align_of_val::<T>(&element) > Self::INLINE_ALIGN {
+            return Err(element);
+        }
+
+        self.with_raw(|raw| raw.try_replace_elem(element, index))
+    }
+
+    /// Moves the elements into a `Vechonk`, which allocates if they are still inline
+    pub fn into_vechonk(mut self) -> Vechonk<T> {
+        self.spill(0);
+
+        match self.storage {
+            Storage::Heap(chonk) => chonk,
+            Storage::Inline { .. } => unreachable!("the elements have just been spilled"),
+        }
+    }
+
+    /// Whether `elem` can be pushed into the inline buffer
+    fn fits_inline(&self, elem: &T) -> bool {
+        mem::align_of_val(elem) <= Self::INLINE_ALIGN && self.raw_view().has_space_for(elem)
+    }
+
+    /// Moves all elements into a `Vechonk` on the heap, that has room for `additional` more bytes.
+    /// Does nothing if they have already been spilled
+    fn spill(&mut self, additional: usize) {
+        if self.spilled() {
+            return;
+        }
+
+        let old = self.raw_view_mut();
+        let required = old.elem_size + old.data_section_size() + additional;
+        let mut chonk = Vechonk::with_capacity(cmp::max(old.cap * 2, required));
+
+        for i in 0..old.len {
+            // SAFETY: `i` is in bounds, and the inline storage is overwritten below without touching
+            //         the elements again, so they are moved
+            unsafe { chonk.raw.push_raw(old.get_unchecked_ptr(i)) };
+        }
+        chonk.debug_check_invariants();
+
+        self.storage = Storage::Heap(chonk);
+    }
+
+    /// Calls `f` with the `RawVechonk` of the elements, and writes back how it changed.
+    /// While inline, `f` must not regrow it, and only its length, `elem_size` and `in_order` are written back,
+    /// so those have to be everything that it changes besides the buffer
+    fn with_raw<R>(&mut self, f: impl FnOnce(&mut RawVechonk<T>) -> R) -> R {
+        match &mut self.storage {
+            Storage::Inline {
+                buf,
+                len,
+                elem_size,
+                in_order,
+                ..
+            } => {
+                let mut raw =
+                    Self::inline_raw(buf.0.as_mut_ptr().cast(), *len, *elem_size, *in_order);
+                let result = f(&mut raw);
+                debug_assert_eq!(raw.ptr.as_ptr(), buf.0.as_mut_ptr().cast());
+
+                *len = raw.len;
+                *elem_size = raw.elem_size;
+                *in_order = raw.in_order;
+                result
+            }
+            Storage::Heap(chonk) => {
+                let result = f(&mut chonk.raw);
+                chonk.debug_check_invariants();
+                result
+            }
+        }
+    }
+
+    fn inline_raw(ptr: *mut u8, len: usize, elem_size: usize, in_order: bool) -> RawVechonk<T> {
+        let mut raw = RawVechonk::new();
+        // SAFETY: The pointer comes from a reference, which is never null
+        raw.ptr = unsafe { NonNull::new_unchecked(ptr) };
+        raw.len = len;
+        raw.cap = force_align(BYTES, mem::align_of::<PtrData<T>>());
+        raw.elem_size = elem_size;
+        raw.in_order = in_order;
+        raw
+    }
+}
+
+impl<T: ?Sized, const BYTES: usize> Default for SmallVechonk<T, BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: The elements are owned by the `SmallVechonk`, either in the inline buffer or in the `Vechonk`
+unsafe impl<T: ?Sized, const BYTES: usize> RawAccess<T> for SmallVechonk<T, BYTES> {
+    fn raw_view(&self) -> RawVechonk<T> {
+        match &self.storage {
+            Storage::Inline {
+                buf,
+                len,
+                elem_size,
+                in_order,
+                ..
+            } => Self::inline_raw(buf.0.as_ptr() as *mut u8, *len, *elem_size, *in_order),
+            Storage::Heap(chonk) => chonk.raw.copy(),
+        }
+    }
+
+    fn raw_view_mut(&mut self) -> RawVechonk<T> {
+        match &mut self.storage {
+            Storage::Inline {
+                buf,
+                len,
+                elem_size,
+                in_order,
+                ..
+            } => Self::inline_raw(buf.0.as_mut_ptr().cast(), *len, *elem_size, *in_order),
+            Storage::Heap(chonk) => chonk.raw.copy(),
+        }
+    }
+}
+
+impl_access!([T: ?Sized, const BYTES: usize] SmallVechonk<T, BYTES>, "SmallVechonk");
+
+impl<T: ?Sized, const BYTES: usize> From<SmallVechonk<T, BYTES>> for Vechonk<T> {
+    fn from(small: SmallVechonk<T, BYTES>) -> Self {
+        small.into_vechonk()
+    }
+}
@@ -0,0 +1,97 @@
+//! Introspection into the memory used by a `Vechonk`, none of this allocates
+
+use crate::Vechonk;
+
+/// A summary of how the buffer of a [`Vechonk`] is used, created by [`Vechonk::memory_report`]
+///
+/// `element_bytes + wasted_bytes + metadata_bytes + free_bytes == capacity` always holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryReport {
+    /// The size of the buffer in bytes
+    pub capacity: usize,
+    /// The bytes occupied by the elements themselves
+    pub element_bytes: usize,
+    /// The bytes in the element section that are not occupied by any element. This is the padding needed for
+    /// alignment, and the space left behind by removed, moved or shrunk elements, which can be reclaimed by
+    /// [`Vechonk::compact`]
+    pub wasted_bytes: usize,
+    /// The bytes used for the offset and metadata of the elements
+    pub metadata_bytes: usize,
+    /// The bytes between the element section and the metadata, that can be used by new elements
+    pub free_bytes: usize,
+}
+
+impl<T: ?Sized> Vechonk<T> {
+    /// The size of the element at the index in bytes, O(1)
+    ///
+    /// Panics if the index is out of bounds
+    pub fn elem_size_of(&self, index: usize) -> usize {
+        self.raw.check_index(index);
+
+        // SAFETY: We just did a bounds check
+        unsafe { self.raw.sizeof_elem(index) }
+    }
+
+    /// The offset of the element at the index from the start of the buffer in bytes, O(1)
+    ///
+    /// Panics if the index is out of bounds
+    pub fn elem_offset(&self, index: usize) -> usize {
+        self.raw.check_index(index);
+
+        // SAFETY: We just did a bounds check
+        unsafe { self.raw.get_offset(index) }
+    }
+
+    /// The unused bytes in memory directly in front of the element at the index. This is usually padding,
+    /// but it can also be space left behind by other elements.
+    ///
+    /// The element in front of it in memory is usually the previous element by index, which makes this O(1).
+    /// After the elements have been reordered, it can be any of them, so this is O(n)
+    ///
+    /// Panics if the index is out of bounds
+    pub fn padding_before(&self, index: usize) -> usize {
+        self.raw.check_index(index);
+
+        // SAFETY: We just did a bounds check
+        let (start, offset) = unsafe {
+            (
+                self.raw.free_space_around(index).0,
+                self.raw.get_offset(index),
+            )
+        };
+
+        // zero sized elements might be inside of other elements
+        offset.saturating_sub(start)
+    }
+
+    /// The bytes of the buffer that are in use, including padding and metadata, O(1)
+    pub fn bytes_used(&self) -> usize {
+        self.raw.elem_size + self.metadata_bytes()
+    }
+
+    /// The bytes of the buffer that can be used by new elements and their metadata before regrowing, O(1)
+    pub fn bytes_free(&self) -> usize {
+        self.raw.cap - self.bytes_used()
+    }
+
+    /// The bytes used to store the offset and metadata of the elements, O(1)
+    pub fn metadata_bytes(&self) -> usize {
+        self.raw.data_section_size()
+    }
+
+    /// A summary of how the buffer is used. This has to look at the size of every element, so it's O(n)
+    pub fn memory_report(&self) -> MemoryReport {
+        // SAFETY: All indices are in bounds
+        let element_bytes = (0..self.len())
+            .map(|index| unsafe { self.raw.sizeof_elem(index) })
+            .sum::<usize>();
+
+        MemoryReport {
+            capacity: self.raw.cap,
+            element_bytes,
+            wasted_bytes: self.raw.elem_size - element_bytes,
+            metadata_bytes: self.metadata_bytes(),
+            free_bytes: self.bytes_free(),
+        }
+    }
+}
@@ -1,8 +1,11 @@
 #![cfg(test)]
 
-use core::{cmp::Ordering, hash::Hash};
+use core::{cmp::Ordering, hash::Hash, mem::MaybeUninit};
 
-use crate::{vechonk, Vechonk};
+use crate::{
+    vechonk, AppendOnlyVechonk, ConcurrentVechonk, Doubling, Exact, GrowthStrategy, Increment,
+    OneAndAHalf, SmallVechonk, Vechonk, VechonkBuf,
+};
 use alloc::boxed::Box;
 
 const USIZE_BYTES: usize = (usize::BITS / 8) as usize;
@@ -192,6 +195,27 @@ fn grow_alignment() {
     let _ = chonk[1];
 }
 
+#[test]
+fn grow_alignment_no_overlap() {
+    use core::any::Any;
+
+    let mut chonk = Vechonk::<dyn Any>::new();
+
+    for i in 0..8_u8 {
+        chonk.push(Box::new(i));
+        chonk.push(Box::new(BigAlign(i)));
+    }
+
+    for i in 0..8_u8 {
+        let index = usize::from(i) * 2;
+        assert_eq!(chonk[index].downcast_ref::<u8>(), Some(&i));
+
+        let big = chonk[index + 1].downcast_ref::<BigAlign>().unwrap();
+        assert_eq!(big.0, i);
+        assert_eq!((big as *const BigAlign).addr() % 2048, 0);
+    }
+}
+
 #[test]
 fn popping() {
     let mut chonk = Vechonk::<str>::with_capacity(512);
@@ -232,6 +256,23 @@ fn into_iter() {
     assert_eq!(iter.next(), None);
 }
 
+#[test]
+fn iter_double_ended() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    let mut iter = chonk.iter();
+    assert_eq!(iter.next_back(), Some("owo"));
+    assert_eq!(iter.next(), Some("hello"));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some("uwu"));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(chonk.iter_mut().rev().next().map(|s| &*s), Some("owo"));
+
+    let collected = chonk.into_iter().rev().collect::<Vec<_>>();
+    assert_eq!(collected, ["owo".into(), "uwu".into(), "hello".into()]);
+}
+
 #[test]
 fn partial_eq_eq() {
     let chonk1 = vechonk![235.0.into(), 325.8.into()];
@@ -447,3 +488,2464 @@ fn index_mut_out_of_bounds() {
 
     chonk[3].decrement();
 }
+
+#[test]
+fn swap() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    chonk.swap(0, 2);
+
+    assert_eq!(&chonk[0], "owo");
+    assert_eq!(&chonk[1], "uwu");
+    assert_eq!(&chonk[2], "hello");
+    assert!(!chonk.is_physically_ordered());
+}
+
+#[test]
+#[should_panic]
+fn swap_out_of_bounds() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+    chonk.swap(0, 2);
+}
+
+#[test]
+fn reverse() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    chonk.reverse();
+
+    assert!(chonk.iter().eq(["owo", "uwu", "hello"]));
+}
+
+#[test]
+fn rotate() {
+    let mut chonk: Vechonk<str> = vechonk!["a".into(), "bb".into(), "ccc".into(), "dddd".into()];
+
+    chonk.rotate_left(1);
+    assert!(chonk.iter().eq(["bb", "ccc", "dddd", "a"]));
+
+    chonk.rotate_right(2);
+    assert!(chonk.iter().eq(["dddd", "a", "bb", "ccc"]));
+}
+
+#[test]
+fn permute() {
+    let mut chonk: Vechonk<str> = vechonk!["a".into(), "bb".into(), "ccc".into()];
+
+    chonk.permute(&[2, 0, 1]);
+
+    assert!(chonk.iter().eq(["ccc", "a", "bb"]));
+}
+
+#[test]
+#[should_panic]
+fn permute_duplicate() {
+    let mut chonk: Vechonk<str> = vechonk!["a".into(), "bb".into(), "ccc".into()];
+
+    chonk.permute(&[0, 0, 1]);
+}
+
+#[test]
+fn repack() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    assert!(chonk.is_physically_ordered());
+    chonk.reverse();
+    assert!(!chonk.is_physically_ordered());
+
+    chonk.repack();
+
+    assert!(chonk.is_physically_ordered());
+    assert!(chonk.iter().eq(["owo", "uwu", "hello"]));
+}
+
+#[test]
+fn compact_after_pop_and_replace() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    assert_eq!(chonk.compact(), 0);
+
+    chonk.try_replace(0, "a".into()).unwrap();
+    chonk.swap(1, 2);
+    chonk.pop();
+
+    // 4 bytes from "hello" and the 3 bytes of "uwu" behind "owo"
+    assert_eq!(chonk.compact(), 7);
+    assert_eq!(chonk.memory_report().wasted_bytes, 0);
+    assert!(chonk.iter().eq(["a", "owo"]));
+    assert_eq!(chonk.elem_offset(1), 1);
+
+    chonk.push("new".into());
+    assert!(chonk.iter().eq(["a", "owo", "new"]));
+}
+
+#[test]
+fn compact_keeps_alignment() {
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any> = Vechonk::new();
+    chonk.push_value(1_u8);
+    chonk.push_value(2_u8);
+    chonk.push_value(3_u64);
+    chonk.swap(0, 1);
+    chonk.downcast_pop::<u64>();
+    chonk.push_value(4_u32);
+    chonk.try_replace(0, Box::new(())).unwrap();
+
+    chonk.compact();
+
+    assert_eq!(chonk.get_as::<()>(0), Some(&()));
+    assert_eq!(chonk.get_as::<u8>(1), Some(&1));
+    assert_eq!(chonk.get_as::<u32>(2), Some(&4));
+    let addr = (chonk.get_as::<u32>(2).unwrap() as *const u32).addr();
+    assert_eq!(addr % core::mem::align_of::<u32>(), 0);
+}
+
+#[test]
+fn compact_by_alignment() {
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any> = Vechonk::new();
+    for i in 0..4_u8 {
+        chonk.push_value(i);
+        chonk.push_value(BigAlign(i));
+    }
+
+    let wasted = chonk.memory_report().wasted_bytes;
+    let reclaimed = chonk.compact_by_alignment();
+
+    assert!(reclaimed > 0);
+    assert!(chonk.memory_report().wasted_bytes < wasted);
+
+    for i in 0..4_u8 {
+        assert_eq!(chonk.get_as::<u8>(usize::from(i) * 2), Some(&i));
+        let big = chonk.get_as::<BigAlign>(usize::from(i) * 2 + 1).unwrap();
+        assert_eq!(big.0, i);
+        assert_eq!((big as *const BigAlign).addr() % 2048, 0);
+    }
+
+    chonk.push_value(5_u8);
+    assert_eq!(chonk.get_as::<u8>(8), Some(&5));
+}
+
+#[test]
+fn compact_by_alignment_empty() {
+    let mut chonk: Vechonk<str> = Vechonk::new();
+    assert_eq!(chonk.compact_by_alignment(), 0);
+
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+    chonk.pop();
+    chonk.pop();
+    chonk.compact_by_alignment();
+    assert!(chonk.is_empty());
+}
+
+#[test]
+fn packed_builder() {
+    use crate::PackedBuilder;
+    use core::any::Any;
+
+    let mut builder = PackedBuilder::<dyn Any>::new();
+    for i in 0..4_u8 {
+        builder.push(Box::new(i));
+        builder.push(Box::new(BigAlign(i)));
+        builder.push(Box::new(u64::from(i)));
+    }
+    assert_eq!(builder.len(), 12);
+
+    // padding needed to get from `addr` to the next multiple of 2048
+    let padding_to_big_align = |addr: usize| (2048 - addr % 2048) % 2048;
+
+    // the first `u8` is at the start of the buffer. Every other `BigAlign` follows a `BigAlign`, a `u64` and
+    // a `u8`, which end 9 bytes after an aligned address
+    let naive_addr = builder.as_vechonk().as_ptr().addr();
+    let naive_padding = padding_to_big_align(naive_addr + 1) + 3 * (2048 - 9);
+
+    let (chonk, report) = builder.build();
+
+    // the `BigAlign`s come first, followed by the `u64`s and the `u8`s, which don't need any padding
+    let packed_addr = chonk.as_ptr().addr();
+
+    assert_eq!(report.naive_padding, naive_padding);
+    assert_eq!(report.padding, padding_to_big_align(packed_addr));
+    assert_eq!(report.saved(), report.naive_padding - report.padding);
+    assert_eq!(chonk.memory_report().wasted_bytes, report.padding);
+
+    // the allocation only leaves room for the padding in front of the first element
+    assert!(chonk.bytes_free() + report.padding < 2048);
+
+    for i in 0..4_u8 {
+        let index = usize::from(i) * 3;
+        assert_eq!(chonk.get_as::<u8>(index), Some(&i));
+        assert_eq!(chonk.get_as::<BigAlign>(index + 1).unwrap().0, i);
+        assert_eq!(chonk.get_as::<u64>(index + 2), Some(&u64::from(i)));
+
+        let addr = (chonk.get_as::<BigAlign>(index + 1).unwrap() as *const BigAlign).addr();
+        assert_eq!(addr % 2048, 0);
+    }
+}
+
+#[test]
+fn packed_builder_extend() {
+    use crate::PackedBuilder;
+
+    let mut builder = PackedBuilder::<str>::default();
+    assert!(builder.is_empty());
+
+    let (chonk, report) = PackedBuilder::<str>::new().build();
+    assert!(chonk.is_empty());
+    assert_eq!(report.saved(), 0);
+
+    builder.extend(["hello".into(), "uwu".into()]);
+    let (mut chonk, report) = builder.build();
+
+    assert_eq!(report.padding, 0);
+    assert_eq!(report.naive_padding, 0);
+    assert!(chonk.iter().eq(["hello", "uwu"]));
+
+    chonk.push("owo".into());
+    assert!(chonk.iter().eq(["hello", "uwu", "owo"]));
+}
+
+#[test]
+fn raw_parts_roundtrip() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+    chonk.swap(0, 1);
+    let ptr = chonk.as_mut_ptr();
+
+    let (raw_ptr, len, cap, elem_size) = chonk.into_raw_parts();
+    assert_eq!(raw_ptr, ptr);
+    assert_eq!((len, elem_size), (2, 8));
+
+    // the entry for index 0 is the last one in the buffer
+    let entry_size = core::mem::size_of::<(usize, usize)>();
+    // SAFETY: The entry is inside of the buffer, and aligned
+    let (offset, meta) = unsafe { *raw_ptr.add(cap - entry_size).cast::<(usize, usize)>() };
+    assert_eq!((offset, meta), (5, 3));
+
+    // SAFETY: The parts come from `into_raw_parts`
+    let mut chonk = unsafe { Vechonk::<str>::from_raw_parts(raw_ptr, len, cap, elem_size) };
+
+    assert!(chonk.iter().eq(["uwu", "hello"]));
+    chonk.push("owo".into());
+    assert!(chonk.iter().eq(["uwu", "hello", "owo"]));
+}
+
+#[test]
+fn raw_parts_empty() {
+    let chonk: Vechonk<[u8]> = Vechonk::new();
+    let (ptr, len, cap, elem_size) = chonk.into_raw_parts();
+    assert_eq!((len, cap, elem_size), (0, 0, 0));
+
+    // SAFETY: The parts come from `into_raw_parts`
+    let mut chonk = unsafe { Vechonk::<[u8]>::from_raw_parts(ptr, len, cap, elem_size) };
+    chonk.push_row(&[1, 2]);
+    assert_eq!(chonk.as_ptr(), chonk[0].as_ptr());
+}
+
+#[test]
+fn replace_after_swap() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    chonk.swap(0, 2);
+
+    // "hello" is still physically in front of "uwu", so it can't grow past it
+    chonk.try_replace(2, "hello!".into()).unwrap_err();
+    chonk.try_replace(2, "hewwo".into()).unwrap();
+
+    assert!(chonk.iter().eq(["owo", "uwu", "hewwo"]));
+}
+
+#[test]
+fn replace_in_order_after_repack() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+    assert!(chonk.raw.in_order);
+
+    chonk.reverse();
+    assert!(!chonk.raw.in_order);
+
+    chonk.repack();
+    assert!(chonk.raw.in_order);
+
+    // "uwu" is between the other two, so it can't grow
+    chonk.try_replace(1, "uwu!".into()).unwrap_err();
+    chonk.try_replace(2, "hello, world".into()).unwrap();
+
+    assert!(chonk.iter().eq(["owo", "uwu", "hello, world"]));
+}
+
+#[test]
+fn replace_next_to_zst_inside_of_element() {
+    let mut chonk: Vechonk<dyn core::any::Any> = Vechonk::with_capacity(128);
+    chonk.push_value(1u32);
+    chonk.push_value(());
+    chonk.push_value(2u64);
+
+    // the `u64` covers the `()` behind the `u32`
+    chonk.try_replace(0, Box::new(3u64)).unwrap();
+
+    // the neighbour of the `u64` by index is the `()`, but the free space starts behind the other `u64`
+    chonk.try_replace(2, Box::new([4u8; 12])).unwrap();
+
+    assert_eq!(chonk.get_as::<u64>(0), Some(&3));
+    assert_eq!(chonk.get_as::<[u8; 12]>(2), Some(&[4; 12]));
+    assert_eq!(chonk.check_invariants(), Ok(()));
+}
+
+#[test]
+fn replace_with_overaligned_zst() {
+    #[repr(align(2048))]
+    struct BigZst;
+
+    let mut chonk: Vechonk<dyn core::any::Any> = Vechonk::new();
+    chonk.push_value(1u8);
+
+    // the gap of "1u8" is too small to align a `BigZst` in it
+    assert!(chonk.try_replace(0, Box::new(BigZst)).is_err());
+    assert_eq!(chonk.get_as::<u8>(0), Some(&1));
+    assert_eq!(chonk.check_invariants(), Ok(()));
+}
+
+#[test]
+fn replace_last_then_push() {
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity(128);
+    chonk.push("a".into());
+
+    chonk.try_replace(0, "hello".into()).unwrap();
+    chonk.push("uwu".into());
+
+    assert!(chonk.iter().eq(["hello", "uwu"]));
+}
+
+#[test]
+fn push_after_padding() {
+    use core::any::Any;
+
+    let mut chonk = Vechonk::<dyn Any>::with_capacity(128);
+
+    chonk.push(Box::new(1_u8));
+    chonk.push(Box::new(2_u64));
+    chonk.push(Box::new(3_u8));
+
+    assert_eq!(chonk[1].downcast_ref::<u64>(), Some(&2));
+    assert_eq!(chonk[2].downcast_ref::<u8>(), Some(&3));
+}
+
+#[test]
+fn pop_after_swap() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+    chonk.swap(0, 1);
+
+    assert_eq!(chonk.pop().unwrap().as_ref(), "hello");
+    chonk.push("owo".into());
+
+    assert!(chonk.iter().eq(["uwu", "owo"]));
+}
+
+#[test]
+fn contains() {
+    let chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+    assert!(chonk.contains("uwu"));
+    assert!(!chonk.contains("owo"));
+}
+
+#[test]
+fn starts_ends_with() {
+    let chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    assert!(chonk.starts_with(&[]));
+    assert!(chonk.starts_with(&["hello", "uwu"]));
+    assert!(!chonk.starts_with(&["uwu"]));
+    assert!(chonk.ends_with(&["uwu", "owo"]));
+    assert!(!chonk.ends_with(&["hello"]));
+    assert!(!chonk.ends_with(&["a", "hello", "uwu", "owo"]));
+}
+
+#[test]
+fn binary_search() {
+    let chonk: Vechonk<str> = vechonk!["a".into(), "bb".into(), "c".into(), "ddd".into()];
+
+    assert_eq!(chonk.binary_search("a"), Ok(0));
+    assert_eq!(chonk.binary_search("ddd"), Ok(3));
+    assert_eq!(chonk.binary_search("bc"), Err(2));
+    assert_eq!(chonk.binary_search("e"), Err(4));
+    assert_eq!(Vechonk::<str>::new().binary_search("a"), Err(0));
+}
+
+#[test]
+fn binary_search_by_key() {
+    let chonk: Vechonk<str> = vechonk!["a".into(), "bb".into(), "ccc".into(), "dddd".into()];
+
+    assert_eq!(chonk.binary_search_by_key(&3, |s| s.len()), Ok(2));
+    assert_eq!(chonk.binary_search_by_key(&5, |s| s.len()), Err(4));
+}
+
+#[test]
+fn partition_point() {
+    let chonk: Vechonk<[u8]> = vechonk![[1].into(), [1, 2].into(), [1, 2, 3].into()];
+
+    assert_eq!(chonk.partition_point(|s| s.len() < 2), 1);
+    assert_eq!(chonk.partition_point(|_| true), 3);
+}
+
+#[test]
+fn any_get_as() {
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any> = Vechonk::new();
+    chonk.push(Box::new(5_u32));
+    chonk.push(Box::new("uwu"));
+    chonk.push(Box::new(3.5_f64));
+
+    assert_eq!(chonk.get_as::<u32>(0), Some(&5));
+    assert_eq!(chonk.get_as::<u64>(0), None);
+    assert_eq!(chonk.get_as::<&str>(1), Some(&"uwu"));
+    assert_eq!(chonk.get_as::<u32>(3), None);
+
+    *chonk.get_as_mut::<f64>(2).unwrap() += 1.0;
+    assert_eq!(chonk.get_as::<f64>(2), Some(&4.5));
+    assert_eq!(chonk.get_as_mut::<u8>(2), None);
+}
+
+#[test]
+fn any_iter_of() {
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any + Send> = Vechonk::new();
+
+    for i in 0..10_u64 {
+        chonk.push_value(i);
+        chonk.push_value(i as u8);
+        chonk.push_value(());
+    }
+
+    assert_eq!(chonk.len(), 30);
+    assert_eq!(chonk.iter_of::<u64>().sum::<u64>(), 45);
+    assert_eq!(chonk.iter_of::<u8>().count(), 10);
+    assert_eq!(chonk.iter_of::<()>().count(), 10);
+    assert_eq!(chonk.iter_of::<u16>().count(), 0);
+}
+
+#[test]
+fn any_push_value_alignment() {
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any + Send + Sync> = Vechonk::new();
+
+    chonk.push_value(1_u8);
+    chonk.push_value(2_u128);
+    chonk.push_value([3_u16; 5]);
+
+    let addr = (chonk.get_as::<u128>(1).unwrap() as *const u128).addr();
+    assert_eq!(addr % core::mem::align_of::<u128>(), 0);
+    assert_eq!(chonk.get_as::<[u16; 5]>(2), Some(&[3; 5]));
+}
+
+#[test]
+fn any_downcast_pop() {
+    use alloc::string::String;
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any> = Vechonk::new();
+    assert_eq!(chonk.downcast_pop::<u32>(), None);
+
+    chonk.push_value(String::from("hello"));
+    chonk.push_value(5_u32);
+
+    assert_eq!(chonk.downcast_pop::<String>(), None);
+    assert_eq!(chonk.len(), 2);
+    assert_eq!(chonk.downcast_pop::<u32>(), Some(5));
+    assert_eq!(chonk.downcast_pop::<String>(), Some(String::from("hello")));
+    assert!(chonk.is_empty());
+
+    // the memory of popped elements is reused
+    chonk.push_value(6_u32);
+    assert_eq!(chonk.raw.elem_size, 4);
+}
+
+#[test]
+fn jagged_push_row() {
+    let mut chonk: Vechonk<[f64]> = Vechonk::new();
+
+    chonk.push_row(&[1.0, 2.0]);
+    chonk.push_row(&[]);
+    chonk.push_row_iter((0..5).map(f64::from));
+
+    assert_eq!(chonk.len(), 3);
+    assert_eq!(&chonk[0], &[1.0, 2.0]);
+    assert!(chonk[1].is_empty());
+    assert_eq!(&chonk[2], &[0.0, 1.0, 2.0, 3.0, 4.0]);
+
+    assert_eq!(chonk.row_len(0), 2);
+    assert_eq!(chonk.row_len(1), 0);
+    assert_eq!(chonk.row_len(2), 5);
+    assert_eq!(chonk.total_items(), 7);
+
+    assert_eq!(
+        chonk.flat_iter().copied().collect::<Vec<_>>(),
+        [1.0, 2.0, 0.0, 1.0, 2.0, 3.0, 4.0]
+    );
+}
+
+#[test]
+fn jagged_push_row_wrong_len() {
+    struct Lying(core::ops::Range<u16>);
+
+    impl Iterator for Lying {
+        type Item = u16;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl ExactSizeIterator for Lying {
+        fn len(&self) -> usize {
+            1
+        }
+    }
+
+    let mut chonk: Vechonk<[u16]> = Vechonk::new();
+    chonk.push_row_iter(Lying(0..4));
+    chonk.push_row_iter(Lying(0..0));
+
+    assert_eq!(&chonk[0], &[0, 1, 2, 3]);
+    assert!(chonk[1].is_empty());
+}
+
+#[test]
+#[should_panic]
+fn jagged_row_len_out_of_bounds() {
+    let mut chonk: Vechonk<[u8]> = Vechonk::new();
+    chonk.push_row(&[1]);
+
+    chonk.row_len(1);
+}
+
+#[test]
+fn jagged_rows_mut() {
+    let mut chonk: Vechonk<[u32]> = Vechonk::new();
+    for i in 0..8 {
+        chonk.push_row_iter(0..i);
+    }
+
+    let rows = chonk.rows_mut().collect::<Vec<_>>();
+    std::thread::scope(|scope| {
+        for row in rows {
+            scope.spawn(|| row.iter_mut().for_each(|item| *item *= 2));
+        }
+    });
+
+    for (i, row) in chonk.iter().enumerate() {
+        assert!(row.iter().copied().eq((0..i as u32).map(|item| item * 2)));
+    }
+}
+
+#[test]
+fn jagged_extend_row() {
+    let mut chonk: Vechonk<[u64]> = Vechonk::new();
+    chonk.push_row(&[1, 2]);
+    chonk.push_row(&[3]);
+
+    // the last row grows in place
+    let last_ptr = chonk[1].as_ptr();
+    chonk.extend_row(1, &[4, 5]);
+    assert_eq!(chonk[1].as_ptr(), last_ptr);
+    assert_eq!(&chonk[1], &[3, 4, 5]);
+
+    // the first row has to move
+    chonk.extend_row(0, &[6]);
+    assert_eq!(&chonk[0], &[1, 2, 6]);
+    assert_eq!(&chonk[1], &[3, 4, 5]);
+
+    chonk.extend_row(0, &[]);
+    assert_eq!(chonk.total_items(), 6);
+}
+
+#[test]
+fn jagged_extend_moved_row() {
+    let mut chonk: Vechonk<[u64]> = Vechonk::with_capacity(256);
+    chonk.push_row(&[1, 2]);
+    chonk.push_row(&[3]);
+
+    chonk.extend_row(0, &[4]);
+    assert!(!chonk.is_physically_ordered());
+
+    // after moving, the first row is at the end of the buffer, so it grows in place
+    let first_ptr = chonk[0].as_ptr();
+    chonk.extend_row(0, &[5, 6]);
+    assert_eq!(chonk[0].as_ptr(), first_ptr);
+    assert_eq!(&chonk[0], &[1, 2, 4, 5, 6]);
+    assert_eq!(&chonk[1], &[3]);
+}
+
+#[test]
+fn jagged_push_many_rows() {
+    let mut chonk: Vechonk<[u64]> = Vechonk::new();
+
+    for i in 0..1000_u32 {
+        chonk.push_row_iter((0..i % 7).map(u64::from));
+    }
+
+    // every row is written right behind the previous one
+    assert!(chonk.is_physically_ordered());
+    assert_eq!(chonk.memory_report().wasted_bytes, 0);
+    assert!((0..1000).all(|i| chonk[i].iter().copied().eq(0..i as u64 % 7)));
+}
+
+#[test]
+fn stats_elements() {
+    use core::any::Any;
+
+    let mut chonk: Vechonk<dyn Any> = Vechonk::new();
+    chonk.push_value(1_u8);
+    chonk.push_value(2_u64);
+    chonk.push_value(());
+
+    assert_eq!(chonk.elem_size_of(0), 1);
+    assert_eq!(chonk.elem_size_of(1), 8);
+    assert_eq!(chonk.elem_size_of(2), 0);
+
+    assert_eq!(chonk.elem_offset(0), 0);
+    assert_eq!(chonk.elem_offset(1), 8);
+
+    assert_eq!(chonk.padding_before(0), 0);
+    assert_eq!(chonk.padding_before(1), 7);
+}
+
+#[test]
+fn stats_padding_after_swap() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+    chonk.swap(0, 2);
+
+    // the physical neighbours don't change when swapping
+    assert_eq!(chonk.elem_offset(2), 0);
+    assert_eq!(chonk.padding_before(0), 0);
+    assert_eq!(chonk.padding_before(1), 0);
+
+    // "hello" was at the front, so its space is free now
+    chonk.pop();
+    assert_eq!(chonk.padding_before(1), 5);
+    chonk.swap(0, 1);
+    chonk.pop();
+    assert_eq!(chonk.padding_before(0), 5);
+}
+
+#[test]
+#[should_panic]
+fn stats_out_of_bounds() {
+    let chonk: Vechonk<str> = vechonk!["hello".into()];
+
+    chonk.elem_offset(1);
+}
+
+#[test]
+fn stats_memory_report() {
+    let mut chonk: Vechonk<[u16]> = Vechonk::with_capacity(512);
+    let empty = chonk.memory_report();
+    assert_eq!(empty.capacity, 512);
+    assert_eq!(empty.free_bytes, 512);
+    assert_eq!(chonk.bytes_used(), 0);
+
+    chonk.push_row(&[1; 3]);
+    chonk.push_row(&[2; 5]);
+    chonk.push_row(&[3; 1]);
+    chonk.swap(0, 2);
+    chonk.pop();
+
+    let report = chonk.memory_report();
+    assert_eq!(report.element_bytes, 12);
+    assert_eq!(report.wasted_bytes, 6);
+    assert_eq!(report.metadata_bytes, chonk.metadata_bytes());
+    assert_eq!(report.metadata_bytes, 2 * 2 * core::mem::size_of::<usize>());
+    assert_eq!(chonk.bytes_used(), 18 + report.metadata_bytes);
+    assert_eq!(report.free_bytes, chonk.bytes_free());
+    assert_eq!(
+        report.element_bytes + report.wasted_bytes + report.metadata_bytes + report.free_bytes,
+        report.capacity
+    );
+
+    chonk.repack();
+    assert_eq!(chonk.memory_report().wasted_bytes, 0);
+}
+
+#[test]
+fn growth_exact() {
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity_and_strategy(0, &Exact);
+
+    chonk.push("hello".into());
+    // 5 bytes + one metadata entry, rounded up to the alignment of the metadata
+    assert_eq!(chonk.raw.cap, 24);
+
+    chonk.push("uwu".into());
+    assert_eq!(chonk.raw.cap, 40);
+    assert_eq!(chonk.bytes_free(), 0);
+}
+
+#[test]
+fn growth_builtin_strategies() {
+    fn caps_after_pushes(strategy: &'static dyn GrowthStrategy) -> [usize; 3] {
+        let mut chonk: Vechonk<[u8]> = Vechonk::with_capacity_and_strategy(64, strategy);
+        [60, 60, 60].map(|len| {
+            chonk.push(vec![0; len].into());
+            chonk.raw.cap
+        })
+    }
+
+    // each push needs 76 bytes
+    assert_eq!(caps_after_pushes(&Doubling), [128, 256, 256]);
+    assert_eq!(caps_after_pushes(&OneAndAHalf), [96, 152, 232]);
+    assert_eq!(caps_after_pushes(&Increment(100)), [168, 168, 272]);
+    assert_eq!(caps_after_pushes(&Exact), [80, 152, 232]);
+}
+
+#[test]
+fn growth_custom_strategy_survives_relocation() {
+    struct Quadrupling;
+
+    impl GrowthStrategy for Quadrupling {
+        fn grow(&self, current: usize, required: usize) -> usize {
+            core::cmp::max(current * 4, required)
+        }
+    }
+
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity_and_strategy(64, &Quadrupling);
+    chonk.push("hello".into());
+    chonk.repack();
+    chonk.compact_by_alignment();
+
+    let mut tail = chonk.split_off(0);
+    // `split_off` allocates just enough for "hello"
+    assert_eq!(tail.raw.cap, 24);
+    tail.push("a".repeat(20).into());
+    assert_eq!(tail.raw.cap, 96);
+
+    chonk.push("a".repeat(100).into());
+    assert_eq!(chonk.raw.cap, 256);
+
+    chonk.set_growth_strategy(&Exact);
+    chonk.push("a".repeat(200).into());
+    assert_eq!(chonk.raw.cap, 336);
+}
+
+#[test]
+fn capacity_for_str_batch() {
+    let batch = ["hello", "", "uwu", "a longer string"];
+    let bytes = batch.iter().map(|s| s.len()).sum();
+
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity_for(batch.len(), bytes);
+    let (ptr, cap) = (chonk.as_ptr(), chonk.raw.cap);
+
+    batch.iter().for_each(|&s| chonk.push(s.into()));
+    assert_eq!((chonk.as_ptr(), chonk.raw.cap), (ptr, cap));
+    // strings don't need padding
+    assert!(chonk.bytes_free() < USIZE_BYTES);
+}
+
+#[test]
+fn capacity_for_overaligned_slices() {
+    let mut chonk: Vechonk<[BigAlign]> = Vechonk::with_capacity_for(3, 3 * 2048);
+    let (ptr, cap) = (chonk.as_ptr(), chonk.raw.cap);
+
+    chonk.push(Box::new([BigAlign(1)]));
+    chonk.push(Box::new([]));
+    chonk.push(Box::new([BigAlign(2), BigAlign(3)]));
+    assert_eq!((chonk.as_ptr(), chonk.raw.cap), (ptr, cap));
+}
+
+#[test]
+fn capacity_for_mixed_alignments() {
+    let mut chonk: Vechonk<dyn core::any::Any> = Vechonk::with_capacity_for(4, 1 + 8 + 2048 + 2);
+    let (ptr, cap) = (chonk.as_ptr(), chonk.raw.cap);
+
+    chonk.push_value(1u8);
+    chonk.push_value(2u64);
+    chonk.push_value(BigAlign(3));
+    chonk.push_value(4u16);
+    assert_eq!((chonk.as_ptr(), chonk.raw.cap), (ptr, cap));
+}
+
+#[test]
+fn capacity_for_overaligned_zst_regrows() {
+    #[repr(align(2048))]
+    struct BigZst;
+
+    let mut chonk: Vechonk<dyn core::any::Any> = Vechonk::with_capacity_for(2, 1);
+    let cap = chonk.raw.cap;
+
+    chonk.push_value(1u8);
+    assert_eq!(chonk.raw.cap, cap);
+
+    // the padding in front of `BigZst` isn't part of the reserved capacity
+    chonk.push_value(BigZst);
+    assert!(chonk.raw.cap > cap);
+    assert!(chonk.get_as::<BigZst>(1).is_some());
+    assert_eq!(chonk.check_invariants(), Ok(()));
+}
+
+#[test]
+fn reserve_for() {
+    let mut chonk: Vechonk<[u64]> = Vechonk::new();
+    chonk.push(Box::new([1, 2, 3]));
+
+    chonk.reserve_for(10, 10 * 8);
+    let (ptr, cap) = (chonk.as_ptr(), chonk.raw.cap);
+
+    // there is enough space already
+    chonk.reserve_for(5, 5 * 8);
+    assert_eq!((chonk.as_ptr(), chonk.raw.cap), (ptr, cap));
+
+    (0..10).for_each(|i| chonk.push(Box::new([i])));
+    assert_eq!((chonk.as_ptr(), chonk.raw.cap), (ptr, cap));
+    assert_eq!(chonk.len(), 11);
+    assert_eq!(&chonk[0], &[1, 2, 3]);
+}
+
+#[test]
+fn reserve_for_exact_growth() {
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity_and_strategy(0, &Exact);
+    chonk.push("hello".into());
+
+    chonk.reserve_for(2, 6);
+    chonk.push("uwu".into());
+    chonk.push("owo".into());
+    // only rounded up to the alignment of the metadata
+    assert!(chonk.bytes_free() < USIZE_BYTES);
+}
+
+#[test]
+#[should_panic]
+fn capacity_for_overflow() {
+    let _: Vechonk<str> = Vechonk::with_capacity_for(usize::MAX / 8, 0);
+}
+
+#[test]
+fn invariants_hold_after_mutations() {
+    let mut chonk: Vechonk<dyn core::any::Any> = Vechonk::new();
+    chonk.push_value(1u8);
+    chonk.push_value(BigAlign(2));
+    chonk.push_value(());
+    chonk.push_value(3u64);
+    assert_eq!(chonk.check_invariants(), Ok(()));
+
+    chonk.swap(0, 3);
+    chonk.pop();
+    chonk.try_replace(1, Box::new(4u16)).unwrap();
+    assert_eq!(chonk.check_invariants(), Ok(()));
+
+    chonk.compact_by_alignment();
+    assert_eq!(chonk.check_invariants(), Ok(()));
+
+    let other = chonk.split_off(1);
+    assert_eq!(chonk.check_invariants(), Ok(()));
+    assert_eq!(other.check_invariants(), Ok(()));
+}
+
+#[test]
+fn invariants_detect_corruption() {
+    use crate::InvariantError;
+
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+    let elem_size = chonk.raw.elem_size;
+    chonk.raw.elem_size = chonk.raw.cap;
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::MetadataOverlap)
+    );
+
+    chonk.raw.elem_size = 6;
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::ElementOutOfBounds(1))
+    );
+    chonk.raw.elem_size = elem_size;
+
+    // SAFETY: Both indices are in bounds, and "hello" is a valid element
+    unsafe {
+        let hello = chonk.raw.get_unchecked_ptr(0);
+        chonk.raw.write_meta_data(hello, 1, 1);
+    }
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::ElementOverlap(0, 1))
+    );
+}
+
+#[test]
+fn invariants_detect_offset_outside_of_buffer() {
+    use crate::InvariantError;
+
+    let chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+    // SAFETY: The index is in bounds, only the offset is broken
+    unsafe {
+        let uwu = chonk.raw.get_unchecked_ptr(1);
+        chonk.raw.write_meta_data(uwu, usize::MAX - 1, 1);
+    }
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::ElementOutOfBounds(1))
+    );
+
+    // SAFETY: See above, the metadata of "hello" is valid for `str` as well
+    unsafe {
+        let hello = chonk.raw.get_unchecked_ptr(0);
+        chonk.raw.write_meta_data(hello, chonk.raw.cap, 1);
+    }
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::ElementOutOfBounds(1))
+    );
+}
+
+#[test]
+fn invariants_detect_wrong_order() {
+    use crate::InvariantError;
+
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+    chonk.swap(0, 1);
+    assert_eq!(chonk.check_invariants(), Ok(()));
+
+    chonk.raw.in_order = true;
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::ElementOutOfOrder(1))
+    );
+}
+
+#[test]
+fn invariants_detect_misaligned_element() {
+    use crate::InvariantError;
+
+    let mut chonk: Vechonk<[u32]> = Vechonk::new();
+    chonk.push(Box::new([1, 2]));
+
+    // SAFETY: The index is in bounds, and the moved element still lies inside of the element section
+    unsafe {
+        let elem = chonk.raw.get_unchecked_ptr(0);
+        chonk.raw.write_meta_data(elem, 1, 0);
+    }
+    chonk.raw.elem_size += 1;
+
+    assert_eq!(
+        chonk.check_invariants(),
+        Err(InvariantError::MisalignedElement(0))
+    );
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use crate::{vechonk, DeserializeElem, Vechonk};
+    use alloc::boxed::Box;
+    use serde::Deserialize;
+
+    trait Shape {
+        fn area(&self) -> u64;
+    }
+
+    #[derive(Deserialize)]
+    struct Square(u64);
+
+    impl Shape for Square {
+        fn area(&self) -> u64 {
+            self.0 * self.0
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Rect(u8, u64);
+
+    impl Shape for Rect {
+        fn area(&self) -> u64 {
+            u64::from(self.0) * self.1
+        }
+    }
+
+    #[derive(Deserialize)]
+    enum ShapeRegistry {
+        Square(Square),
+        Rect(Rect),
+    }
+
+    impl<'de> DeserializeElem<'de> for dyn Shape {
+        fn deserialize_into<D>(deserializer: D, chonk: &mut Vechonk<Self>) -> Result<(), D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            match ShapeRegistry::deserialize(deserializer)? {
+                ShapeRegistry::Square(square) => chonk.push(Box::new(square)),
+                ShapeRegistry::Rect(rect) => chonk.push(Box::new(rect)),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn serialize_str() {
+        let chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+        assert_eq!(serde_json::to_string(&chonk).unwrap(), r#"["hello","uwu"]"#);
+    }
+
+    #[test]
+    fn roundtrip_str() {
+        let chonk: Vechonk<str> = vechonk!["hello".into(), "".into(), "uwu".into()];
+
+        let json = serde_json::to_string(&chonk).unwrap();
+        let deserialized: Vechonk<str> = serde_json::from_str(&json).unwrap();
+
+        assert!(chonk.eq(&deserialized));
+    }
+
+    #[test]
+    fn roundtrip_slice() {
+        let chonk: Vechonk<[u16]> = vechonk![[1, 2].into(), [].into(), [3, 4, 5].into()];
+
+        let json = serde_json::to_string(&chonk).unwrap();
+        let deserialized: Vechonk<[u16]> = serde_json::from_str(&json).unwrap();
+
+        assert!(chonk.eq(&deserialized));
+    }
+
+    #[test]
+    fn deserialize_slices_in_place() {
+        let chonk: Vechonk<[u64]> = serde_json::from_str("[[1, 2], [], [3, 4, 5], [6]]").unwrap();
+
+        // every row is written right behind the previous one, without moving
+        assert!(chonk.is_physically_ordered());
+        assert_eq!(chonk.memory_report().wasted_bytes, 0);
+        assert_eq!(chonk.total_items(), 6);
+        assert_eq!(chonk[2], [3, 4, 5]);
+    }
+
+    #[test]
+    fn deserialize_slice_of_strings() {
+        let chonk: Vechonk<[alloc::string::String]> =
+            serde_json::from_str(r#"[["a", "b"], ["c"]]"#).unwrap();
+
+        assert_eq!(chonk[0], ["a", "b"]);
+        assert_eq!(chonk[1], ["c"]);
+    }
+
+    #[test]
+    fn deserialize_trait_object() {
+        let chonk: Vechonk<dyn Shape> =
+            serde_json::from_str(r#"[{ "Square": 3 }, { "Rect": [2, 5] }]"#).unwrap();
+
+        assert_eq!(chonk.len(), 2);
+        assert_eq!(chonk[0].area(), 9);
+        assert_eq!(chonk[1].area(), 10);
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        assert!(serde_json::from_str::<Vechonk<str>>(r#"["a", 1]"#).is_err());
+        assert!(serde_json::from_str::<Vechonk<[u8]>>(r#"[[1], [256]]"#).is_err());
+    }
+}
+
+mod view {
+    use crate::{vechonk, FormatError, Vechonk, VechonkView};
+    use alloc::vec::Vec;
+
+    /// A tiny xorshift rng, so that the fuzz tests are reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, max: usize) -> usize {
+            (self.next() % max as u64) as usize
+        }
+    }
+
+    fn strings() -> Vechonk<str> {
+        vechonk!["hello".into(), "".into(), "uwu".into(), "ünicode".into()]
+    }
+
+    /// A valid view must never hand out elements outside of the image
+    fn check_view(bytes: &[u8]) {
+        if let Ok(view) = VechonkView::<str>::from_bytes(bytes) {
+            let range = bytes.as_ptr_range();
+            for elem in view.iter() {
+                assert!(range.contains(&elem.as_ptr()) || elem.is_empty());
+                assert!(elem.len() <= bytes.len());
+            }
+        }
+    }
+
+    #[test]
+    fn roundtrip_str() {
+        let chonk = strings();
+        let bytes = chonk.to_bytes();
+        let view = VechonkView::<str>::from_bytes(&bytes).unwrap();
+
+        assert_eq!(view.len(), 4);
+        assert!(view.iter().eq(chonk.iter()));
+        assert_eq!(view.get(3), Some("ünicode"));
+        assert_eq!(view.get(4), None);
+    }
+
+    #[test]
+    fn roundtrip_slice() {
+        let chonk: Vechonk<[u32]> = vechonk![[1, 2].into(), [].into(), [u32::MAX].into()];
+        let bytes = chonk.to_bytes();
+        let view = VechonkView::<[u32]>::from_bytes(&bytes).unwrap();
+
+        assert!(view.iter().eq(chonk.iter()));
+    }
+
+    #[test]
+    fn roundtrip_empty() {
+        let bytes = Vechonk::<[u8]>::new().to_bytes();
+        let view = VechonkView::<[u8]>::from_bytes(&bytes).unwrap();
+
+        assert!(view.is_empty());
+        assert_eq!(view.iter().next(), None);
+    }
+
+    #[test]
+    fn zero_copy() {
+        let bytes = strings().to_bytes();
+        let view = VechonkView::<str>::from_bytes(&bytes).unwrap();
+
+        assert!(bytes
+            .as_ptr_range()
+            .contains(&view.get(0).unwrap().as_ptr()));
+    }
+
+    #[test]
+    fn wrong_element_type() {
+        let bytes = strings().to_bytes();
+
+        assert_eq!(
+            VechonkView::<[u8]>::from_bytes(&bytes).err(),
+            Some(FormatError::WrongElementType)
+        );
+
+        let chonk: Vechonk<[u16]> = vechonk![[1, 2].into()];
+        assert_eq!(
+            VechonkView::<[u32]>::from_bytes(&chonk.to_bytes()).err(),
+            Some(FormatError::WrongElementType)
+        );
+    }
+
+    #[test]
+    fn bad_header() {
+        let bytes = strings().to_bytes();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(
+            VechonkView::<str>::from_bytes(&bad_magic).err(),
+            Some(FormatError::BadMagic)
+        );
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 2;
+        assert_eq!(
+            VechonkView::<str>::from_bytes(&bad_version).err(),
+            Some(FormatError::UnsupportedVersion(2))
+        );
+
+        let mut bad_endian = bytes;
+        bad_endian[5] = 3;
+        assert_eq!(
+            VechonkView::<str>::from_bytes(&bad_endian).err(),
+            Some(FormatError::WrongEndianness)
+        );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut bytes = strings().to_bytes();
+        let len = bytes.len();
+        bytes[len - 1] = 0xff;
+
+        assert_eq!(
+            VechonkView::<str>::from_bytes(&bytes).err(),
+            Some(FormatError::InvalidElement)
+        );
+    }
+
+    #[test]
+    fn offsets_out_of_bounds() {
+        let chonk: Vechonk<str> = vechonk!["ab".into(), "c".into()];
+        let mut bytes = chonk.to_bytes();
+
+        // the offset of the second element goes past the end, but the last one is still correct
+        bytes[40..48].copy_from_slice(&100_u64.to_ne_bytes());
+
+        assert_eq!(
+            VechonkView::<str>::from_bytes(&bytes).err(),
+            Some(FormatError::InvalidOffsets)
+        );
+    }
+
+    #[test]
+    fn truncated_and_trailing() {
+        let bytes = strings().to_bytes();
+
+        for len in 0..bytes.len() {
+            assert!(VechonkView::<str>::from_bytes(&bytes[..len]).is_err());
+        }
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert_eq!(
+            VechonkView::<str>::from_bytes(&trailing).err(),
+            Some(FormatError::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn fuzz_mutations() {
+        let bytes = strings().to_bytes();
+        let mut rng = Rng(0x5eed);
+
+        let iterations = if cfg!(miri) { 50 } else { 5000 };
+
+        for _ in 0..iterations {
+            let mut mutated = bytes.clone();
+
+            for _ in 0..=rng.below(4) {
+                let index = rng.below(mutated.len());
+                mutated[index] = rng.next() as u8;
+            }
+
+            check_view(&mutated);
+        }
+    }
+
+    #[test]
+    fn fuzz_random_bytes() {
+        let mut rng = Rng(0xc40c);
+
+        let iterations = if cfg!(miri) { 50 } else { 5000 };
+
+        for _ in 0..iterations {
+            let len = rng.below(128);
+            let mut bytes: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+
+            // give it a chance to get past the header checks
+            if bytes.len() >= 8 {
+                bytes[..8].copy_from_slice(&strings().to_bytes()[..8]);
+            }
+
+            check_view(&bytes);
+        }
+    }
+}
+
+#[test]
+fn split_off() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    let tail = chonk.split_off(1);
+
+    assert!(chonk.iter().eq(["hello"]));
+    assert!(tail.iter().eq(["uwu", "owo"]));
+    assert_eq!(chonk.raw.elem_size, 5);
+
+    chonk.push("hewwo".into());
+    assert!(chonk.iter().eq(["hello", "hewwo"]));
+}
+
+#[test]
+fn split_off_edges() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+
+    assert!(chonk.split_off(2).is_empty());
+
+    let all = chonk.split_off(0);
+    assert!(chonk.is_empty());
+    assert!(all.iter().eq(["hello", "uwu"]));
+}
+
+#[test]
+#[should_panic]
+fn split_off_out_of_bounds() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into()];
+
+    chonk.split_off(2);
+}
+
+#[test]
+fn split_off_after_reverse() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+    chonk.reverse();
+
+    let tail = chonk.split_off(2);
+
+    assert!(chonk.iter().eq(["owo", "uwu"]));
+    assert!(tail.iter().eq(["hello"]));
+}
+
+#[test]
+fn append() {
+    let mut chonk1: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+    let mut chonk2: Vechonk<str> = vechonk!["owo".into(), "hewwo".into()];
+
+    chonk1.append(&mut chonk2);
+
+    assert!(chonk1.iter().eq(["hello", "uwu", "owo", "hewwo"]));
+    assert!(chonk2.is_empty());
+
+    chonk2.push("again".into());
+    chonk1.append(&mut chonk2);
+    assert!(chonk1.iter().eq(["hello", "uwu", "owo", "hewwo", "again"]));
+}
+
+#[test]
+fn append_to_empty() {
+    let mut chonk1: Vechonk<str> = Vechonk::new();
+    let mut chonk2: Vechonk<str> = vechonk!["owo".into(), "hewwo".into()];
+
+    chonk1.append(&mut chonk2);
+    chonk1.append(&mut Vechonk::new());
+
+    assert!(chonk1.iter().eq(["owo", "hewwo"]));
+}
+
+#[test]
+fn append_alignment() {
+    use core::any::Any;
+
+    let mut chonk1 = Vechonk::<dyn Any>::new();
+    chonk1.push(Box::new(1_u8));
+
+    let mut chonk2 = Vechonk::<dyn Any>::new();
+    chonk2.push(Box::new(2_u8));
+    chonk2.push(Box::new(BigAlign(3)));
+    chonk2.push(Box::new(4_u64));
+
+    chonk1.append(&mut chonk2);
+
+    assert_eq!(chonk1.len(), 4);
+    assert_eq!(chonk1[1].downcast_ref::<u8>(), Some(&2));
+    assert_eq!(chonk1[2].downcast_ref::<BigAlign>().unwrap().0, 3);
+    assert_eq!(chonk1[3].downcast_ref::<u64>(), Some(&4));
+    assert_eq!((&chonk1[2] as *const dyn Any).cast::<u8>().addr() % 2048, 0);
+
+    chonk1.push(Box::new(5_u16));
+    assert_eq!(chonk1[4].downcast_ref::<u16>(), Some(&5));
+}
+
+#[test]
+fn dedup() {
+    let mut chonk: Vechonk<str> = vechonk![
+        "a".into(),
+        "a".into(),
+        "bb".into(),
+        "a".into(),
+        "ccc".into(),
+        "ccc".into(),
+        "ccc".into()
+    ];
+
+    chonk.dedup();
+
+    assert!(chonk.iter().eq(["a", "bb", "a", "ccc"]));
+    assert_eq!(chonk.raw.elem_size, 7);
+
+    chonk.push("d".into());
+    assert!(chonk.iter().eq(["a", "bb", "a", "ccc", "d"]));
+}
+
+#[test]
+fn dedup_by_key() {
+    let mut chonk: Vechonk<str> =
+        vechonk!["a".into(), "b".into(), "cc".into(), "dd".into(), "e".into()];
+
+    chonk.dedup_by_key(|s| s.len());
+
+    assert!(chonk.iter().eq(["a", "cc", "e"]));
+}
+
+#[test]
+fn dedup_after_reverse() {
+    let mut chonk: Vechonk<str> = vechonk!["a".into(), "bb".into(), "bb".into(), "ccc".into()];
+    chonk.reverse();
+
+    chonk.dedup();
+
+    assert!(chonk.iter().eq(["ccc", "bb", "a"]));
+    assert_eq!(chonk.raw.elem_size, 6);
+}
+
+#[test]
+fn dedup_alignment() {
+    use core::any::Any;
+
+    let mut chonk = Vechonk::<dyn Any>::new();
+    chonk.push(Box::new(1_u8));
+    chonk.push(Box::new(1_u8));
+    chonk.push(Box::new(2_u64));
+    chonk.push(Box::new(3_u8));
+
+    chonk.dedup_by(|a, b| a.downcast_ref::<u8>().is_some() && b.downcast_ref::<u8>().is_some());
+
+    assert_eq!(chonk.len(), 3);
+    assert_eq!(chonk[0].downcast_ref::<u8>(), Some(&1));
+    assert_eq!(chonk[1].downcast_ref::<u64>(), Some(&2));
+    assert_eq!(chonk[2].downcast_ref::<u8>(), Some(&3));
+}
+
+#[test]
+fn dedup_drops_duplicates() {
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+
+    let mut chonk = Vechonk::<DropCounter>::new();
+    for _ in 0..4 {
+        chonk.push(Box::new(DropCounter(drops.clone())));
+    }
+
+    chonk.dedup_by(|_, _| true);
+
+    assert_eq!(chonk.len(), 1);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn resize_str_in_place() {
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity(128);
+    chonk.push("hello".into());
+
+    let mut hello = chonk.get_resizable(0).unwrap();
+    hello.push_str(" world");
+    hello.push('!');
+    assert_eq!(&*hello, "hello world!");
+
+    hello.truncate(5);
+    assert_eq!(&*hello, "hello");
+
+    assert_eq!(chonk.raw.elem_size, 12);
+    chonk.push("uwu".into());
+    assert!(chonk.iter().eq(["hello", "uwu"]));
+}
+
+#[test]
+fn resize_str_moves() {
+    let mut chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+    chonk.get_resizable(0).unwrap().push_str(", world");
+    chonk.get_resizable(1).unwrap().push_str("wu");
+
+    assert!(chonk.iter().eq(["hello, world", "uwuwu", "owo"]));
+    assert!(!chonk.is_physically_ordered());
+
+    chonk.push("hewwo".into());
+    assert!(chonk.iter().eq(["hello, world", "uwuwu", "owo", "hewwo"]));
+}
+
+#[test]
+fn resize_last_str_char_by_char() {
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity(64);
+    chonk.push("hello".into());
+    chonk.push("".into());
+
+    let mut line = chonk.get_resizable(1).unwrap();
+    for ch in "uwu, owo".chars() {
+        line.push(ch);
+    }
+    assert_eq!(chonk.elem_offset(1), 5);
+
+    // the line grows past the capacity, so it has to regrow
+    let mut line = chonk.get_resizable(1).unwrap();
+    for _ in 0..100 {
+        line.push('!');
+    }
+
+    assert_eq!(&chonk[0], "hello");
+    assert_eq!(&chonk[1][..8], "uwu, owo");
+    assert_eq!(chonk[1].len(), 108);
+    assert_eq!(chonk.check_invariants(), Ok(()));
+}
+
+#[test]
+fn resize_empty_str_left_behind() {
+    let mut chonk: Vechonk<str> = Vechonk::with_capacity(128);
+    chonk.push("hello".into());
+    chonk.push("".into());
+    chonk.try_replace(0, "hi".into()).unwrap();
+    chonk.compact();
+
+    // the empty string stayed at the end of the old "hello", which is where "uwu" ends
+    chonk.push("uwu".into());
+    assert_eq!(chonk.elem_offset(1), chonk.raw.elem_size);
+
+    chonk.get_resizable(1).unwrap().push_str("owo");
+
+    assert!(chonk.iter().eq(["hi", "owo", "uwu"]));
+    assert_eq!(chonk.check_invariants(), Ok(()));
+}
+
+#[test]
+#[should_panic]
+fn resize_str_truncate_char_boundary() {
+    let mut chonk: Vechonk<str> = vechonk!["ü".into()];
+
+    chonk.get_resizable(0).unwrap().truncate(1);
+}
+
+#[test]
+fn resize_slice() {
+    let mut chonk: Vechonk<[u64]> = vechonk![[1].into(), [2, 3].into()];
+
+    let mut first = chonk.get_resizable(0).unwrap();
+    first.push(4);
+    first.extend_from_slice(&[5, 6, 7]);
+    assert_eq!(&*first, [1, 4, 5, 6, 7]);
+
+    first.truncate(2);
+    first[1] = 10;
+
+    assert_eq!(chonk[0], [1, 10]);
+    assert_eq!(chonk[1], [2, 3]);
+    assert!(chonk.get_resizable(2).is_none());
+}
+
+#[test]
+fn resize_slice_drops() {
+    use alloc::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut chonk = Vechonk::<[Rc<()>]>::new();
+    chonk.push(Box::new([]));
+
+    let mut elem = chonk.get_resizable(0).unwrap();
+    for _ in 0..10 {
+        elem.push(rc.clone());
+    }
+    assert_eq!(Rc::strong_count(&rc), 11);
+
+    elem.truncate(3);
+    assert_eq!(Rc::strong_count(&rc), 4);
+}
+
+#[test]
+fn append_only_stable_references() {
+    let chonk = AppendOnlyVechonk::<str>::new();
+
+    let hello = chonk.push("hello".into());
+    let refs = (0..100)
+        .map(|i| chonk.push(alloc::format!("element {}", i).into()))
+        .collect::<alloc::vec::Vec<_>>();
+
+    assert_eq!(hello, "hello");
+    assert_eq!(refs[42], "element 42");
+    assert_eq!(chonk.len(), 101);
+    assert!(chonk.chunk_count() > 1);
+
+    assert_eq!(&chonk[0], "hello");
+    assert_eq!(chonk.get(100), Some("element 99"));
+    assert_eq!(chonk.get(101), None);
+}
+
+#[test]
+fn append_only_iter() {
+    let chonk = AppendOnlyVechonk::<[u8]>::with_capacity(0);
+
+    for i in 0..50 {
+        chonk.push(alloc::vec![i; i as usize].into());
+    }
+
+    assert!(chonk.iter().enumerate().all(|(i, elem)| elem.len() == i));
+
+    // elements that are pushed while iterating are yielded as well
+    let mut iter = chonk.iter().skip(49);
+    assert_eq!(iter.next().map(|elem| elem.len()), Some(49));
+    chonk.push(Box::new([1, 2, 3]));
+    assert_eq!(iter.next(), Some(&[1, 2, 3][..]));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn append_only_alignment() {
+    use core::any::Any;
+
+    let chonk = AppendOnlyVechonk::<dyn Any>::with_capacity(32);
+
+    let small = chonk.push(Box::new(1_u8));
+    let big = chonk.push(Box::new(BigAlign(2)));
+    chonk.push(Box::new(()));
+    let last = chonk.push(Box::new(3_u64));
+
+    assert_eq!(small.downcast_ref::<u8>(), Some(&1));
+    assert_eq!(big.downcast_ref::<BigAlign>().unwrap().0, 2);
+    assert_eq!((big as *const dyn Any).cast::<u8>().addr() % 2048, 0);
+    assert_eq!(last.downcast_ref::<u64>(), Some(&3));
+    assert!(chonk[2].is::<()>());
+}
+
+#[test]
+#[should_panic]
+fn append_only_index_out_of_bounds() {
+    let chonk = AppendOnlyVechonk::<str>::new();
+    chonk.push("hello".into());
+
+    let _ = &chonk[1];
+}
+
+#[test]
+fn concurrent_push_get() {
+    let chonk = ConcurrentVechonk::<str>::new();
+    assert!(chonk.is_empty());
+    assert!(chonk.get(0).is_none());
+
+    let hello = chonk.push("hello".into());
+    chonk.push("uwu".into());
+
+    assert_eq!(hello, "hello");
+    assert_eq!(chonk.len(), 2);
+    assert_eq!(chonk.get(1), Some("uwu"));
+    assert!(chonk.get(2).is_none());
+}
+
+#[test]
+fn concurrent_many_chunks() {
+    let chonk = ConcurrentVechonk::<[u64]>::new();
+    let count = if cfg!(miri) { 50 } else { 1000 };
+
+    let refs = (0..count)
+        .map(|i| chonk.push(vec![i; i as usize % 7].into_boxed_slice()))
+        .collect::<Vec<_>>();
+
+    for (i, elem) in refs.into_iter().enumerate() {
+        assert_eq!(elem, &vec![i as u64; i % 7][..]);
+        assert_eq!(chonk.get(i), Some(elem));
+    }
+
+    assert_eq!(chonk.len(), count as usize);
+    assert_eq!(chonk.iter().count(), count as usize);
+}
+
+#[test]
+fn concurrent_threads() {
+    let chonk = ConcurrentVechonk::<str>::new();
+    let threads = if cfg!(miri) { 3 } else { 8 };
+    let per_thread = if cfg!(miri) { 20 } else { 2000 };
+
+    std::thread::scope(|scope| {
+        for thread in 0..threads {
+            let chonk = &chonk;
+            scope.spawn(move || {
+                for i in 0..per_thread {
+                    let string = format!("{}-{}", thread, i);
+                    assert_eq!(chonk.push(string.as_str().into()), string);
+                }
+            });
+        }
+
+        // read while the others are pushing, everything that's visible must be initialized
+        scope.spawn(|| {
+            for _ in 0..10 {
+                for elem in chonk.iter() {
+                    assert!(elem.contains('-'));
+                }
+            }
+        });
+    });
+
+    let mut all = chonk.iter().collect::<Vec<_>>();
+    assert_eq!(all.len(), threads * per_thread);
+    assert_eq!(chonk.len(), threads * per_thread);
+
+    all.sort_unstable();
+    let mut expected = (0..threads)
+        .flat_map(|thread| (0..per_thread).map(move |i| format!("{}-{}", thread, i)))
+        .collect::<Vec<_>>();
+    expected.sort_unstable();
+
+    assert_eq!(all, expected);
+}
+
+#[test]
+fn concurrent_alignment() {
+    let chonk = ConcurrentVechonk::<dyn core::any::Any + Send + Sync>::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for i in 0..100_u8 {
+                    chonk.push(Box::new(i));
+                    chonk.push(Box::new(u128::from(i)));
+                }
+            });
+        }
+    });
+
+    for elem in chonk.iter() {
+        let addr = (elem as *const dyn core::any::Any).cast::<u8>().addr();
+        if elem.is::<u128>() {
+            assert_eq!(addr % core::mem::align_of::<u128>(), 0);
+        } else {
+            assert!(elem.is::<u8>());
+        }
+    }
+    assert_eq!(chonk.len(), 800);
+}
+
+#[test]
+fn buf_push_get_iter() {
+    let mut buf = [MaybeUninit::uninit(); 128];
+    let mut chonk = VechonkBuf::<str>::new(&mut buf);
+
+    chonk.try_push_str("hello").unwrap();
+    chonk.try_push_str("").unwrap();
+    chonk.try_push_str("uwu").unwrap();
+
+    assert_eq!(chonk.len(), 3);
+    assert_eq!(&chonk[0], "hello");
+    assert_eq!(chonk.get(2), Some("uwu"));
+    assert_eq!(chonk.get(3), None);
+    assert_eq!(chonk.iter().collect::<Vec<_>>(), ["hello", "", "uwu"]);
+}
+
+#[test]
+fn buf_full() {
+    let mut buf = [MaybeUninit::uninit(); 64];
+    let mut chonk = VechonkBuf::<str>::new(&mut buf);
+
+    let mut pushed = 0;
+    while chonk.try_push_str("hello").is_ok() {
+        pushed += 1;
+    }
+
+    // every string takes 5 bytes + 2 usize of metadata
+    assert_eq!(pushed, chonk.capacity() / (5 + 2 * USIZE_BYTES));
+    assert_eq!(chonk.len(), pushed);
+    assert_eq!(chonk.try_push_str("hello"), Err("hello"));
+    assert_eq!(
+        chonk.bytes_free(),
+        chonk.capacity() - pushed * (5 + 2 * USIZE_BYTES)
+    );
+
+    chonk.clear();
+    assert!(chonk.is_empty());
+    assert_eq!(chonk.bytes_free(), chonk.capacity());
+    chonk.try_push_str("hello").unwrap();
+    assert_eq!(&chonk[0], "hello");
+}
+
+#[test]
+fn buf_unaligned_start() {
+    let mut buf = [0_usize; 8].map(|_| MaybeUninit::<usize>::uninit());
+    // SAFETY: `MaybeUninit<u8>` has no requirements on the bytes
+    let bytes = unsafe {
+        core::slice::from_raw_parts_mut(buf.as_mut_ptr().cast::<MaybeUninit<u8>>(), 8 * USIZE_BYTES)
+    };
+
+    let mut chonk = VechonkBuf::<[u8]>::new(&mut bytes[1..7 * USIZE_BYTES - 2]);
+
+    // the first bytes are skipped to align the start, and the last ones to align the end
+    assert_eq!(chonk.capacity(), 5 * USIZE_BYTES);
+
+    chonk.try_push_slice(&[1, 2, 3]).unwrap();
+    chonk.try_push_slice(&[4]).unwrap();
+    assert_eq!(chonk.iter().collect::<Vec<_>>(), [&[1, 2, 3][..], &[4]]);
+}
+
+#[test]
+fn buf_too_small() {
+    let mut buf = [MaybeUninit::uninit(); 3];
+    let mut chonk = VechonkBuf::<[u8]>::new(&mut buf);
+
+    assert_eq!(chonk.capacity(), 0);
+    assert_eq!(chonk.try_push_slice(&[]), Err(&[][..]));
+}
+
+#[test]
+fn buf_values() {
+    let mut buf = [MaybeUninit::uninit(); 4096 + 128];
+    let mut chonk = VechonkBuf::<dyn core::any::Any>::new(&mut buf);
+
+    chonk.try_push_value(1_u8).unwrap();
+    assert!(chonk.try_push_value(BigAlign(2)).is_ok());
+    chonk.try_push_value(String::from("uwu")).unwrap();
+
+    let big = chonk[1].downcast_ref::<BigAlign>().unwrap();
+    assert_eq!(big.0, 2);
+    assert_eq!((big as *const BigAlign).addr() % 2048, 0);
+    assert_eq!(chonk[2].downcast_ref::<String>().unwrap(), "uwu");
+
+    // the buffer is too small for a second `BigAlign` after the padding
+    let rejected = chonk.try_push_value(BigAlign(3)).unwrap_err();
+    assert_eq!(rejected.0, 3);
+    assert_eq!(chonk.len(), 3);
+
+    let string = chonk.pop().unwrap().downcast::<String>().unwrap();
+    assert_eq!(*string, "uwu");
+}
+
+#[test]
+fn buf_boxes() {
+    let mut buf = [MaybeUninit::uninit(); 64];
+    let mut chonk = VechonkBuf::<[u16]>::new(&mut buf);
+
+    chonk.try_push(vec![1, 2, 3].into()).unwrap();
+    chonk.try_push(vec![4, 5].into()).unwrap();
+
+    let rejected = chonk.try_push(vec![0; 32].into()).unwrap_err();
+    assert_eq!(rejected.len(), 32);
+
+    for elem in chonk.iter_mut() {
+        elem[0] *= 10;
+    }
+    chonk[1][1] = 50;
+
+    assert_eq!(chonk.pop().as_deref(), Some(&[40, 50][..]));
+    assert_eq!(chonk.pop().as_deref(), Some(&[10, 2, 3][..]));
+    assert_eq!(chonk.pop(), None);
+}
+
+#[test]
+fn small_inline_then_spill() {
+    let mut chonk = SmallVechonk::<str, 64>::new();
+
+    chonk.push("hello".into());
+    chonk.push("uwu".into());
+    assert!(!chonk.spilled());
+    assert_eq!(chonk.iter().collect::<Vec<_>>(), ["hello", "uwu"]);
+
+    chonk.push("does not fit".into());
+    assert!(chonk.spilled());
+    assert_eq!(
+        chonk.iter().collect::<Vec<_>>(),
+        ["hello", "uwu", "does not fit"]
+    );
+
+    assert_eq!(chonk.pop().as_deref(), Some("does not fit"));
+    assert_eq!(chonk.pop().as_deref(), Some("uwu"));
+    assert_eq!(chonk.pop().as_deref(), Some("hello"));
+    assert_eq!(chonk.pop(), None);
+    assert!(chonk.spilled());
+}
+
+#[test]
+fn small_pop_inline() {
+    let mut chonk = SmallVechonk::<[u8], 128>::default();
+
+    chonk.push(Box::new([1, 2, 3]));
+    chonk.push(Box::new([4]));
+
+    assert_eq!(chonk.pop().as_deref(), Some(&[4][..]));
+    chonk.push(Box::new([5, 6]));
+
+    assert!(!chonk.spilled());
+    assert_eq!(chonk.len(), 2);
+    assert_eq!(&chonk[1], [5, 6]);
+    assert_eq!(chonk.get(2), None);
+}
+
+#[test]
+fn small_stays_aligned_when_moved() {
+    fn check(chonk: &SmallVechonk<dyn core::any::Any, 128>) {
+        assert_eq!(chonk[0].downcast_ref::<u8>(), Some(&1));
+        let big = chonk[1].downcast_ref::<u128>().unwrap();
+        assert_eq!(*big, 2);
+        assert_eq!(
+            (big as *const u128).addr() % core::mem::align_of::<u128>(),
+            0
+        );
+    }
+
+    let mut chonk = SmallVechonk::<dyn core::any::Any, 128>::new();
+    chonk.push(Box::new(1_u8));
+    chonk.push(Box::new(2_u128));
+    assert!(!chonk.spilled());
+    check(&chonk);
+
+    let moved = Box::new(chonk);
+    check(&moved);
+
+    let moved = vec![SmallVechonk::new(), *moved];
+    check(&moved[1]);
+}
+
+#[test]
+fn small_overaligned_spills() {
+    let mut chonk = SmallVechonk::<dyn core::any::Any, 4096>::new();
+    chonk.push(Box::new(1_u8));
+    chonk.push(Box::new(BigAlign(2)));
+
+    assert!(chonk.spilled());
+    let big = chonk[1].downcast_ref::<BigAlign>().unwrap();
+    assert_eq!(big.0, 2);
+    assert_eq!((big as *const BigAlign).addr() % 2048, 0);
+}
+
+#[test]
+fn small_try_replace() {
+    let mut chonk = SmallVechonk::<dyn core::any::Any, 256>::new();
+    chonk.push(Box::new(1_u64));
+    chonk.push(Box::new(2_u8));
+
+    let old = chonk.try_replace(0, Box::new(3_u32)).unwrap();
+    assert_eq!(old.downcast_ref::<u64>(), Some(&1));
+    assert_eq!(chonk[0].downcast_ref::<u32>(), Some(&3));
+
+    let rejected = chonk.try_replace(1, Box::new(BigAlign(4))).unwrap_err();
+    assert_eq!(rejected.downcast_ref::<BigAlign>().unwrap().0, 4);
+    assert!(!chonk.spilled());
+
+    assert!(chonk.try_replace(2, Box::new(5_u8)).is_err());
+}
+
+#[test]
+fn small_mutation() {
+    let mut chonk = SmallVechonk::<[u16], 64>::new();
+    chonk.push(Box::new([1, 2]));
+    chonk.push(Box::new([3]));
+
+    for elem in chonk.iter_mut() {
+        elem[0] *= 10;
+    }
+    chonk.get_mut(0).unwrap()[1] = 20;
+    chonk[1][0] += 1;
+
+    assert_eq!(chonk.iter().collect::<Vec<_>>(), [&[10, 20][..], &[31]]);
+}
+
+#[test]
+fn small_into_vechonk() {
+    let mut chonk = SmallVechonk::<str, 64>::new();
+    chonk.push("hello".into());
+    chonk.push("uwu".into());
+
+    let chonk: Vechonk<str> = chonk.into();
+    assert!(chonk == vechonk!["hello".into(), "uwu".into()]);
+}
+
+#[test]
+fn small_send_sync() {
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    // `Send` and `Sync` are derived from `Vechonk` and the inline buffer
+    assert_send::<SmallVechonk<str, 64>>();
+    assert_sync::<SmallVechonk<str, 64>>();
+    assert_send::<SmallVechonk<dyn core::any::Any + Send, 64>>();
+    assert_sync::<SmallVechonk<[u8], 0>>();
+}
+
+#[test]
+#[should_panic(expected = "Out of bounds, index 2 for len 2")]
+fn small_index_out_of_bounds() {
+    let mut chonk = SmallVechonk::<str, 64>::new();
+    chonk.push("hello".into());
+    chonk.push("uwu".into());
+
+    let _ = &mut chonk[2];
+}
+
+#[test]
+fn iterate_references() {
+    let chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into()];
+    let mut small = SmallVechonk::<str, 64>::new();
+    let mut buf = [MaybeUninit::uninit(); 64];
+    let mut in_buf = VechonkBuf::<str>::new(&mut buf);
+
+    for elem in &chonk {
+        small.push(elem.into());
+        in_buf.try_push_str(elem).unwrap();
+    }
+
+    assert!((&small).into_iter().eq(&chonk));
+    assert!((&in_buf).into_iter().eq(&chonk));
+}
+
+#[cfg(all(feature = "ffi", feature = "debug-invariants"))]
+mod ffi {
+    use crate::ffi::*;
+
+    #[test]
+    fn push_checks_invariants() {
+        let bytes = vechonk_bytes_new();
+        let strings = vechonk_str_new();
+
+        // SAFETY: Both come from their `*_new` function, and the pointers are valid for the lengths
+        unsafe {
+            for i in 0..100_u8 {
+                let elem = [i; 3];
+                assert!(vechonk_bytes_push(bytes, elem.as_ptr(), usize::from(i % 4)));
+                assert!(vechonk_str_push(
+                    strings,
+                    "uwu".as_ptr(),
+                    usize::from(i % 4)
+                ));
+            }
+            assert_eq!(vechonk_bytes_len(bytes), 100);
+            assert_eq!(vechonk_str_len(strings), 100);
+
+            vechonk_bytes_free(bytes);
+            vechonk_str_free(strings);
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+mod rayon {
+    use crate::{vechonk, Vechonk};
+    use alloc::boxed::Box;
+    use alloc::string::ToString;
+    use rayon::prelude::*;
+
+    trait Feature: Send + Sync {
+        fn score(&self) -> u64;
+        fn bump(&mut self);
+    }
+
+    impl Feature for u64 {
+        fn score(&self) -> u64 {
+            *self
+        }
+
+        fn bump(&mut self) {
+            *self += 1;
+        }
+    }
+
+    impl Feature for u8 {
+        fn score(&self) -> u64 {
+            u64::from(*self)
+        }
+
+        fn bump(&mut self) {
+            *self += 1;
+        }
+    }
+
+    fn features(count: usize) -> Vechonk<dyn Feature> {
+        let mut chonk: Vechonk<dyn Feature> = Vechonk::new();
+
+        for i in 0..count {
+            if i % 2 == 0 {
+                chonk.push(Box::new(i as u64));
+            } else {
+                chonk.push(Box::new((i % 5) as u8));
+            }
+        }
+
+        chonk
+    }
+
+    #[test]
+    fn par_iter() {
+        let chonk = features(1000);
+
+        let sequential = chonk.iter().map(|f| f.score()).sum::<u64>();
+        let parallel = chonk.par_iter().map(|f| f.score()).sum::<u64>();
+
+        assert_eq!(sequential, parallel);
+        assert_eq!(chonk.par_iter().len(), 1000);
+    }
+
+    #[test]
+    fn par_iter_enumerate_zip() {
+        let chonk = features(500);
+        let other = features(500);
+
+        chonk.par_iter().enumerate().for_each(|(i, f)| {
+            assert_eq!(f.score(), chonk[i].score());
+        });
+
+        assert!(chonk
+            .par_iter()
+            .zip(other.par_iter())
+            .all(|(a, b)| a.score() == b.score()));
+    }
+
+    #[test]
+    fn par_iter_mut() {
+        let mut chonk = features(1000);
+        let before = chonk.iter().map(|f| f.score()).collect::<Vec<_>>();
+
+        chonk.par_iter_mut().for_each(|f| f.bump());
+
+        chonk
+            .iter()
+            .zip(before)
+            .for_each(|(f, before)| assert_eq!(f.score(), before + 1));
+    }
+
+    #[test]
+    fn into_par_iter_collect() {
+        let chonk: Vechonk<str> = vechonk!["hello".into(), "uwu".into(), "owo".into()];
+
+        let upper: Vechonk<str> = chonk
+            .into_par_iter()
+            .map(|s| s.to_uppercase().into_boxed_str())
+            .collect();
+
+        assert!(upper.eq(&vechonk!["HELLO".into(), "UWU".into(), "OWO".into()]));
+    }
+
+    #[test]
+    fn collect_in_order() {
+        let chonk: Vechonk<str> = (0..1000)
+            .into_par_iter()
+            .map(|i| i.to_string().into_boxed_str())
+            .collect();
+
+        assert_eq!(chonk.len(), 1000);
+        chonk
+            .iter()
+            .enumerate()
+            .for_each(|(i, s)| assert_eq!(s, i.to_string()));
+    }
+
+    #[test]
+    fn into_par_iter_take() {
+        let chonk: Vechonk<[u32]> = (0..100_u32)
+            .map(|i| vec![i; 3].into_boxed_slice())
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .collect();
+
+        let firsts = chonk
+            .into_par_iter()
+            .rev()
+            .take(3)
+            .map(|s| s[0])
+            .collect::<Vec<_>>();
+        assert_eq!(firsts, [99, 98, 97]);
+    }
+}
+
+#[cfg(feature = "interner")]
+mod interner {
+    use crate::interner::Interner;
+    use std::collections::hash_map::RandomState;
+
+    #[test]
+    fn intern() {
+        let mut interner = Interner::new();
+
+        let hello = interner.intern("hello");
+        let uwu = interner.intern("uwu");
+        let empty = interner.intern("");
+
+        assert_ne!(hello, uwu);
+        assert_eq!(interner.intern("hello"), hello);
+        assert_eq!(interner.intern(""), empty);
+        assert_eq!(interner.len(), 3);
+
+        assert_eq!(interner.resolve(hello), "hello");
+        assert_eq!(interner.resolve(uwu), "uwu");
+        assert_eq!(interner.resolve(empty), "");
+    }
+
+    #[test]
+    fn get() {
+        let mut interner = Interner::new();
+        let hello = interner.intern("hello");
+
+        assert_eq!(interner.get("hello"), Some(hello));
+        assert_eq!(interner.get("uwu"), None);
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn many_strings() {
+        let mut interner = Interner::with_hasher(RandomState::new());
+
+        let symbols = (0..1000)
+            .map(|i| interner.intern(&format!("string {}", i % 500)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(interner.len(), 500);
+        assert_eq!(symbols[3], symbols[503]);
+        assert_eq!(symbols[499].as_u32(), 499);
+        assert_eq!(interner.resolve(symbols[42]), "string 42");
+        assert!(interner
+            .strings()
+            .iter()
+            .eq((0..500).map(|i| format!("string {}", i))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn resolve_foreign_symbol() {
+        let mut interner = Interner::new();
+        let symbol = interner.intern("hello");
+
+        Interner::new().resolve(symbol);
+    }
+}
+
+mod differential {
+    //! Runs random sequences of operations on a `Vechonk<dyn Shape>` and on a `Vec<Box<dyn Shape>>` as the model,
+    //! and checks that they always agree. Under Miri only a few cases are run unless `PROPTEST_CASES` is set:
+    //! `cargo miri test differential`
+
+    use super::BigAlign;
+    use crate::{Doubling, Exact, GrowthStrategy, Increment, OneAndAHalf, Vechonk};
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+    use core::mem;
+    use proptest::prelude::*;
+
+    trait Shape {
+        fn value(&self) -> u64;
+
+        fn set_value(&mut self, value: u64);
+    }
+
+    struct Zst;
+
+    #[repr(align(2048))]
+    struct BigZst;
+
+    impl Shape for Zst {
+        fn value(&self) -> u64 {
+            0
+        }
+
+        fn set_value(&mut self, _: u64) {}
+    }
+
+    impl Shape for BigZst {
+        fn value(&self) -> u64 {
+            0
+        }
+
+        fn set_value(&mut self, _: u64) {}
+    }
+
+    impl Shape for u8 {
+        fn value(&self) -> u64 {
+            u64::from(*self)
+        }
+
+        fn set_value(&mut self, value: u64) {
+            *self = value as u8;
+        }
+    }
+
+    impl Shape for u64 {
+        fn value(&self) -> u64 {
+            *self
+        }
+
+        fn set_value(&mut self, value: u64) {
+            *self = value;
+        }
+    }
+
+    impl Shape for [u8; 3] {
+        fn value(&self) -> u64 {
+            self.iter().map(|&byte| u64::from(byte)).sum()
+        }
+
+        fn set_value(&mut self, value: u64) {
+            *self = [value as u8, (value >> 8) as u8, (value >> 16) as u8];
+        }
+    }
+
+    impl Shape for [u16; 3] {
+        fn value(&self) -> u64 {
+            self.iter().map(|&word| u64::from(word)).sum()
+        }
+
+        fn set_value(&mut self, value: u64) {
+            *self = [value as u16, (value >> 16) as u16, (value >> 32) as u16];
+        }
+    }
+
+    impl Shape for BigAlign {
+        fn value(&self) -> u64 {
+            u64::from(self.0)
+        }
+
+        fn set_value(&mut self, value: u64) {
+            self.0 = value as u8;
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Kind {
+        Zst,
+        BigZst,
+        Byte,
+        Word,
+        Bytes,
+        Words,
+        BigAlign,
+    }
+
+    impl Kind {
+        fn make(self, value: u64) -> Box<dyn Shape> {
+            let mut elem: Box<dyn Shape> = match self {
+                Kind::Zst => Box::new(Zst),
+                Kind::BigZst => Box::new(BigZst),
+                Kind::Byte => Box::new(0u8),
+                Kind::Word => Box::new(0u64),
+                Kind::Bytes => Box::new([0u8; 3]),
+                Kind::Words => Box::new([0u16; 3]),
+                Kind::BigAlign => Box::new(BigAlign(0)),
+            };
+            elem.set_value(value);
+            elem
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(Kind, u64),
+        Pop,
+        TryReplace(usize, Kind, u64),
+        GetMut(usize, u64),
+        Iter,
+    }
+
+    fn kind() -> impl Strategy<Value = Kind> {
+        prop_oneof![
+            Just(Kind::Zst),
+            Just(Kind::BigZst),
+            Just(Kind::Byte),
+            Just(Kind::Word),
+            Just(Kind::Bytes),
+            Just(Kind::Words),
+            Just(Kind::BigAlign),
+        ]
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            4 => (kind(), any::<u64>()).prop_map(|(kind, value)| Op::Push(kind, value)),
+            1 => Just(Op::Pop),
+            2 => (any::<usize>(), kind(), any::<u64>())
+                .prop_map(|(index, kind, value)| Op::TryReplace(index, kind, value)),
+            1 => (any::<usize>(), any::<u64>()).prop_map(|(index, value)| Op::GetMut(index, value)),
+            1 => Just(Op::Iter),
+        ]
+    }
+
+    fn config() -> ProptestConfig {
+        let config = ProptestConfig::default();
+
+        if !cfg!(miri) {
+            return config;
+        }
+
+        // Miri can't read the regressions file with isolation enabled
+        let cases = match std::env::var_os("PROPTEST_CASES") {
+            Some(_) => config.cases,
+            None => 4,
+        };
+
+        ProptestConfig {
+            cases,
+            failure_persistence: None,
+            ..config
+        }
+    }
+
+    fn check(chonk: &Vechonk<dyn Shape>, model: &[Box<dyn Shape>]) -> Result<(), TestCaseError> {
+        prop_assert_eq!(chonk.check_invariants(), Ok(()));
+        prop_assert_eq!(chonk.len(), model.len());
+        prop_assert!(chonk.get(model.len()).is_none());
+
+        for (elem, expected) in chonk.iter().zip(model) {
+            prop_assert_eq!(elem.value(), expected.value());
+            prop_assert_eq!(mem::size_of_val(elem), mem::size_of_val(&**expected));
+            prop_assert_eq!(mem::align_of_val(elem), mem::align_of_val(&**expected));
+        }
+
+        Ok(())
+    }
+
+    const STRATEGIES: [&dyn GrowthStrategy; 4] = [&Doubling, &OneAndAHalf, &Increment(24), &Exact];
+
+    fn run(capacity: usize, strategy: usize, ops: Vec<Op>) -> Result<(), TestCaseError> {
+        let mut chonk: Vechonk<dyn Shape> =
+            Vechonk::with_capacity_and_strategy(capacity, STRATEGIES[strategy]);
+        let mut model: Vec<Box<dyn Shape>> = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::Push(kind, value) => {
+                    chonk.push(kind.make(value));
+                    model.push(kind.make(value));
+                }
+                Op::Pop => {
+                    let popped = chonk.pop().map(|elem| elem.value());
+                    prop_assert_eq!(popped, model.pop().map(|elem| elem.value()));
+                }
+                Op::TryReplace(_, _, _) | Op::GetMut(_, _) if model.is_empty() => {}
+                Op::TryReplace(index, kind, value) => {
+                    let index = index % model.len();
+
+                    // whether the replacement fits depends on the layout, the model just follows along
+                    match chonk.try_replace(index, kind.make(value)) {
+                        Ok(old) => {
+                            prop_assert_eq!(old.value(), model[index].value());
+                            model[index] = kind.make(value);
+                        }
+                        Err(new) => prop_assert_eq!(new.value(), kind.make(value).value()),
+                    }
+                }
+                Op::GetMut(index, value) => {
+                    let index = index % model.len();
+                    chonk.get_mut(index).unwrap().set_value(value);
+                    model[index].set_value(value);
+                }
+                Op::Iter => {
+                    let values = |iter: &mut dyn Iterator<Item = &dyn Shape>| {
+                        iter.map(|elem| elem.value()).collect::<Vec<_>>()
+                    };
+                    let expected = values(&mut model.iter().map(|elem| &**elem));
+
+                    prop_assert_eq!(values(&mut chonk.iter()), expected.clone());
+                    prop_assert_eq!(
+                        values(&mut chonk.iter().rev()),
+                        expected.into_iter().rev().collect::<Vec<_>>()
+                    );
+                }
+            }
+
+            check(&chonk, &model)?;
+        }
+
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(config())]
+
+        #[test]
+        fn matches_vec(
+            capacity in 0..4096usize,
+            strategy in 0..STRATEGIES.len(),
+            ops in prop::collection::vec(op(), 0..64),
+        ) {
+            run(capacity, strategy, ops)?;
+        }
+
+        #[test]
+        fn matches_vec_without_capacity(ops in prop::collection::vec(op(), 0..64)) {
+            run(0, 0, ops)?;
+        }
+    }
+}
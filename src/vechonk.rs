@@ -0,0 +1,572 @@
+//! The methods and trait impls of [`Vechonk`], which is only available with `alloc`
+
+use crate::access::{impl_access, RawAccess};
+use crate::raw::RawVechonk;
+use crate::{Doubling, ElemMut, GrowthStrategy, IntoIter, Vechonk};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cmp;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr;
+
+// SAFETY: The `Vechonk` owns its elements and the buffer, just like a `Vec`
+unsafe impl<T: ?Sized + Send> Send for Vechonk<T> {}
+// SAFETY: Shared references only hand out shared references to the elements
+unsafe impl<T: ?Sized + Sync> Sync for Vechonk<T> {}
+
+impl<T: ?Sized> Vechonk<T> {
+    /// The amount of elements in the `Vechonk`, O(1)
+    pub const fn len(&self) -> usize {
+        self.raw.len
+    }
+
+    /// Create a new empty Vechonk that doesn't allocate anything
+    pub const fn new() -> Self {
+        Self {
+            raw: RawVechonk::new(),
+        }
+    }
+
+    /// Create a new Vechonk that allocates `capacity` bytes. `capacity` gets shrunken down
+    /// to the next multiple of the alignment of usize + metadata of `T`
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            raw: RawVechonk::with_capacity(capacity),
+        }
+    }
+
+    /// Create a new Vechonk that can hold `elements` elements with a combined size of `element_bytes` bytes
+    /// without regrowing. The space for the metadata and the worst case padding between the elements is added.
+    ///
+    /// For `str` and slices, the padding is known from the item type. For other types like `dyn Trait`, the
+    /// alignment of the elements isn't known up front, so up to their size is reserved as padding, which is the
+    /// worst case for elements that are not zero sized. Zero sized elements with an alignment bigger than the
+    /// elements in front of them, like a `#[repr(align(2048))]` unit struct, can still need up to their alignment
+    /// as padding, so pushing them might regrow the buffer anyways.
+    ///
+    /// Panics if the capacity overflows `usize`
+    pub fn with_capacity_for(elements: usize, element_bytes: usize) -> Self {
+        Self::with_capacity(RawVechonk::<T>::capacity_for(elements, element_bytes))
+    }
+
+    /// Makes sure that `elements` more elements with a combined size of `element_bytes` bytes can be pushed
+    /// without regrowing, see [`Vechonk::with_capacity_for`] for the exception of over aligned zero sized elements.
+    /// If the buffer regrows, the growth strategy
+    /// decides how much bigger it gets. O(n) if it regrows, O(1) otherwise
+    ///
+    /// Panics if the capacity overflows `usize`
+    pub fn reserve_for(&mut self, elements: usize, element_bytes: usize) {
+        self.raw
+            .reserve(RawVechonk::<T>::capacity_for(elements, element_bytes));
+        self.debug_check_invariants();
+    }
+
+    /// Like [`Vechonk::with_capacity`], but grows according to `strategy` instead of doubling
+    pub fn with_capacity_and_strategy(
+        capacity: usize,
+        strategy: &'static dyn GrowthStrategy,
+    ) -> Self {
+        let mut chonk = Self::with_capacity(capacity);
+        chonk.set_growth_strategy(strategy);
+        chonk
+    }
+
+    /// The strategy that decides how much the buffer grows when it's too small
+    pub fn growth_strategy(&self) -> &'static dyn GrowthStrategy {
+        self.raw.growth
+    }
+
+    /// Changes how much the buffer grows when it's too small from now on, see [`GrowthStrategy`]
+    pub fn set_growth_strategy(&mut self, strategy: &'static dyn GrowthStrategy) {
+        self.raw.growth = strategy;
+    }
+
+    /// Pushes a new element into the [`Vechonk`]. Does panic (for now) if there is no more capacity
+    /// todo: don't take a box but some U that can be unsized into T
+    pub fn push(&mut self, element: Box<T>) {
+        self.raw.push(element);
+        self.debug_check_invariants();
+    }
+
+    /// Get the last element, returns `None` if the `Vechonk` is empty
+    pub fn pop(&mut self) -> Option<Box<T>> {
+        let elem = self.raw.pop();
+        self.debug_check_invariants();
+        elem
+    }
+
+    #[cfg(any())]
+    pub fn insert(&mut self, _index: usize, _element: Box<T>) {
+        todo!()
+    }
+
+    /// Replace an element at an index.
+    /// This could be O(n) worst case, if the new element is too big, and every other element needs to be realigned.
+    /// Even worse, after all the copying, it might realloc anyways because it couldn't fit in the space.
+    ///
+    /// Returns the old element at that
+    #[cfg(any())]
+    pub fn replace(&mut self, _index: usize, _element: Box<T>) -> Box<T> {
+        todo!()
+    }
+
+    /// Replace an element at an index.
+    /// * If the replacement was successful without moving the other elements, the old element is returned.
+    /// * If the new element doesn't fit the gap or can't be aligned, it is returned.
+    ///
+    /// This never moves other elements. It's O(1) unless the neighbours of the element are zero sized, but after
+    /// the elements have been reordered with [`Vechonk::swap`] and friends, the gap around the element has to be
+    /// found by looking at the metadata of all other elements, which makes it O(n) until [`Vechonk::repack`]
+    /// lays them out in order again
+    pub fn try_replace(&mut self, index: usize, element: Box<T>) -> Result<Box<T>, Box<T>> {
+        let result = self.raw.try_replace_elem(element, index);
+        self.debug_check_invariants();
+        result
+    }
+
+    /// Swaps two elements. Only the metadata is swapped, the elements themselves stay where they are, O(1)
+    ///
+    /// Panics if `a` or `b` is out of bounds
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.raw.swap(a, b);
+        self.debug_check_invariants();
+    }
+
+    /// Reverses the order of the elements. Only the metadata is reordered, O(n)
+    pub fn reverse(&mut self) {
+        self.raw.reverse();
+        self.debug_check_invariants();
+    }
+
+    /// Rotates the elements such that the element at `mid` becomes the first one.
+    /// Only the metadata is reordered, O(n)
+    ///
+    /// Panics if `mid` is greater than the length
+    pub fn rotate_left(&mut self, mid: usize) {
+        self.raw.rotate_left(mid);
+        self.debug_check_invariants();
+    }
+
+    /// Rotates the elements such that the last `k` elements come first.
+    /// Only the metadata is reordered, O(n)
+    ///
+    /// Panics if `k` is greater than the length
+    pub fn rotate_right(&mut self, k: usize) {
+        self.raw.rotate_right(k);
+        self.debug_check_invariants();
+    }
+
+    /// Reorders the elements such that the element at index `i` is the one that was at index `permutation[i]`.
+    /// Only the metadata is reordered, O(n)
+    ///
+    /// Panics if `permutation` is not a permutation of `0..self.len()`
+    pub fn permute(&mut self, permutation: &[usize]) {
+        self.raw.permute(permutation);
+        self.debug_check_invariants();
+    }
+
+    /// Splits the `Vechonk` into two at the given index. Returns a new `Vechonk` containing the
+    /// elements from `at` onwards, while `self` keeps the elements before `at`
+    ///
+    /// Panics if `at` is greater than the length
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let other = Self {
+            raw: self.raw.split_off(at),
+        };
+        self.debug_check_invariants();
+        other.debug_check_invariants();
+        other
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The elements are copied over with a single copy, and only the metadata is adjusted afterwards
+    pub fn append(&mut self, other: &mut Self) {
+        self.raw.append(&mut other.raw);
+        self.debug_check_invariants();
+        other.debug_check_invariants();
+    }
+
+    /// Removes consecutive repeated elements, see [`alloc::vec::Vec::dedup`]
+    ///
+    /// The removed elements are dropped, and the remaining ones are moved together to reclaim the memory
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.raw.dedup_by(|a, b| a == b);
+        self.debug_check_invariants();
+    }
+
+    /// Removes consecutive elements that resolve to the same key, see [`alloc::vec::Vec::dedup_by_key`]
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.raw.dedup_by(|a, b| key(a) == key(b));
+        self.debug_check_invariants();
+    }
+
+    /// Removes consecutive elements for which `same_bucket` returns `true`, see [`alloc::vec::Vec::dedup_by`]
+    ///
+    /// `same_bucket` gets passed the element and the previous element that is kept, in that order.
+    /// If it returns `true`, the element is removed.
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        self.raw.dedup_by(same_bucket);
+        self.debug_check_invariants();
+    }
+
+    /// Whether the elements are laid out in memory in the same order as their indices, O(n)
+    ///
+    /// This is always the case unless the elements have been reordered with [`Vechonk::swap`] and friends
+    pub fn is_physically_ordered(&self) -> bool {
+        self.raw.is_physically_ordered()
+    }
+
+    /// Copies all elements into a new allocation in the order of their indices, to restore locality after
+    /// reordering them. This also gets rid of the memory from elements that have been popped or replaced.
+    pub fn repack(&mut self) {
+        self.raw.relocate(self.raw.cap);
+        self.debug_check_invariants();
+    }
+
+    /// Moves all elements towards the front of the buffer, in the order they are laid out in memory, so that there
+    /// are no gaps between them except for the padding required by their alignment. This reclaims the memory of
+    /// elements that have been popped, replaced or moved. Returns the amount of bytes that were reclaimed, O(n)
+    ///
+    /// This doesn't allocate, and only the offsets of the elements change, not their order
+    pub fn compact(&mut self) -> usize {
+        let reclaimed = self.raw.compact();
+        self.debug_check_invariants();
+        reclaimed
+    }
+
+    /// Like [`Vechonk::compact`], but lays out the elements by descending alignment to reduce the padding
+    /// between them. The order of the indices is preserved, only the elements are moved.
+    /// Returns the amount of bytes that were reclaimed, O(n log n)
+    ///
+    /// This copies the elements into a new allocation of the same capacity, or a bigger one in the rare case
+    /// that the padding at the start of the new allocation makes them not fit
+    pub fn compact_by_alignment(&mut self) -> usize {
+        let mut order = (0..self.len()).collect::<Vec<_>>();
+        // SAFETY: All indices are in bounds
+        order.sort_by_key(|&i| cmp::Reverse(mem::align_of_val(unsafe { self.get_unchecked(i) })));
+
+        let old_elem_size = self.raw.elem_size;
+        self.raw.relocate_in_order(self.raw.cap, &order);
+        self.debug_check_invariants();
+
+        old_elem_size.saturating_sub(self.raw.elem_size)
+    }
+
+    /// Decomposes the `Vechonk` into its raw parts: the pointer to the buffer, the length,
+    /// the capacity of the buffer in bytes, and the end of the element section in bytes.
+    ///
+    /// The caller is responsible for the memory afterwards. The only way to free it is to
+    /// rebuild the `Vechonk` with [`Vechonk::from_raw_parts`]
+    pub fn into_raw_parts(self) -> (*mut u8, usize, usize, usize) {
+        let chonk = mem::ManuallyDrop::new(self);
+
+        (
+            chonk.raw.ptr.as_ptr(),
+            chonk.raw.len,
+            chonk.raw.cap,
+            chonk.raw.elem_size,
+        )
+    }
+
+    /// Creates a `Vechonk` from its raw parts, see [`Vechonk::into_raw_parts`].
+    /// The growth strategy isn't part of them, it's reset to [`Doubling`]. Neither is whether the elements
+    /// have been reordered, so [`Vechonk::try_replace`] is O(n) until the `Vechonk` is compacted or repacked
+    ///
+    /// The buffer is laid out as shown in the crate level docs:
+    /// * The elements start at the front of the buffer, each aligned for itself, and all of them end before
+    ///   `elem_size`.
+    /// * The end of the buffer contains one entry per element. Each entry is a `#[repr(C)]` struct of the offset of
+    ///   the element from the start of the buffer as a `usize`, followed by the `<T as Pointee>::Metadata` of the
+    ///   element. They are stored backwards, so with `size` being the size of an entry, the entry for index `i`
+    ///   starts at `cap - (i + 1) * size`.
+    ///
+    /// # Safety
+    /// * `ptr` must have been allocated by the global allocator, with a size of `cap` bytes and the alignment of
+    ///   `usize`, or be dangling and aligned if `cap` is 0. `cap` must be a multiple of the alignment of `usize`.
+    /// * `elem_size + len * size` must not be greater than `cap`.
+    /// * Every entry must point to a valid, initialized `T` that is properly aligned and doesn't overlap with
+    ///   other elements, except for zero sized ones.
+    /// * Ownership of the buffer and the elements is transferred to the `Vechonk`.
+    ///
+    /// All of this is upheld by the parts returned from [`Vechonk::into_raw_parts`]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize, cap: usize, elem_size: usize) -> Self {
+        let chonk = Self {
+            raw: RawVechonk {
+                // SAFETY: We can assume that `ptr` is a valid allocation, or dangling, which is never null
+                ptr: unsafe { ptr::NonNull::new_unchecked(ptr) },
+                len,
+                cap,
+                elem_size,
+                growth: &Doubling,
+                // the parts don't tell whether the elements have been reordered
+                in_order: false,
+                _marker: PhantomData,
+            },
+        };
+        chonk.debug_check_invariants();
+        chonk
+    }
+
+    /// A pointer to the start of the buffer. The first element doesn't necessarily start here,
+    /// see [`Vechonk::elem_offset`]
+    pub const fn as_ptr(&self) -> *const u8 {
+        self.raw.ptr.as_ptr()
+    }
+
+    /// A mutable pointer to the start of the buffer. The first element doesn't necessarily start here,
+    /// see [`Vechonk::elem_offset`]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.raw.ptr.as_ptr()
+    }
+
+    /// Get a mutable guard to an element at the index, that can change the length of the element,
+    /// see [`ElemMut`]. Returns `None` if the index is out of bounds
+    pub fn get_resizable(&mut self, index: usize) -> Option<ElemMut<'_, T>> {
+        if index < self.len() {
+            // SAFETY: The index has been checked above
+            unsafe { Some(ElemMut::new(self, index)) }
+        } else {
+            None
+        }
+    }
+
+    /// # Safety
+    /// The index must be in bounds
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        // SAFETY: The metadata is only assigned directly from the pointer metadata of the original object and therefore valid
+        //         The pointer is calculated from the offset, which is also valid
+        //         The pointer is aligned, because it has been aligned manually in `Self::push`
+        unsafe { &mut *self.raw.get_unchecked_ptr(index) }
+    }
+
+    /// # Safety
+    /// The index must be in bounds
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        // SAFETY: The metadata is only assigned directly from the pointer metadata of the original object and therefore valid
+        //         The pointer is calculated from the offset, which is also valid
+        //         The pointer is aligned, because it has been aligned manually in `Self::push`
+        unsafe { &*self.raw.get_unchecked_ptr(index) }
+    }
+
+    /// Whether the `Vechonk` contains an element that is equal to `x`, O(n)
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    /// Whether the first elements are equal to the elements of `needle`
+    pub fn starts_with(&self, needle: &[&T]) -> bool
+    where
+        T: PartialEq,
+    {
+        needle.len() <= self.len() && self.iter().zip(needle).all(|(elem, &x)| elem == x)
+    }
+
+    /// Whether the last elements are equal to the elements of `needle`
+    pub fn ends_with(&self, needle: &[&T]) -> bool
+    where
+        T: PartialEq,
+    {
+        needle.len() <= self.len()
+            && self
+                .iter()
+                .skip(self.len() - needle.len())
+                .zip(needle)
+                .all(|(elem, &x)| elem == x)
+    }
+
+    /// Binary searches this sorted `Vechonk` for `x`, see [`slice::binary_search`]
+    ///
+    /// If the value is found, `Ok` with its index is returned. If there are multiple matches, any of them
+    /// might be returned. If it isn't found, `Err` with the index where it could be inserted while
+    /// keeping the order is returned.
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|elem| elem.cmp(x))
+    }
+
+    /// Binary searches this sorted `Vechonk` with a comparator function, see [`slice::binary_search_by`]
+    ///
+    /// The comparator function should return whether the element is `Less`, `Equal` or `Greater` than the target,
+    /// and the `Vechonk` must be sorted accordingly.
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        // see core::slice::binary_search_by
+
+        let mut size = self.len();
+        let mut left = 0;
+        let mut right = size;
+
+        while left < right {
+            let mid = left + size / 2;
+
+            // SAFETY: `mid` is always in `left..right`, which is in bounds
+            let ordering = f(unsafe { self.get_unchecked(mid) });
+
+            match ordering {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+
+            size = right - left;
+        }
+
+        Err(left)
+    }
+
+    /// Binary searches this `Vechonk`, which is sorted by a key extraction function, see [`slice::binary_search_by_key`]
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|elem| f(elem).cmp(b))
+    }
+
+    /// Returns the index of the first element for which `pred` returns `false`, see [`slice::partition_point`]
+    ///
+    /// The `Vechonk` must be partitioned, so all elements for which `pred` returns `true` must come first
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.binary_search_by(|elem| {
+            if pred(elem) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+    }
+}
+
+// SAFETY: The `Vechonk` owns the elements that `raw` views
+unsafe impl<T: ?Sized> RawAccess<T> for Vechonk<T> {
+    fn raw_view(&self) -> RawVechonk<T> {
+        self.raw.copy()
+    }
+
+    fn raw_view_mut(&mut self) -> RawVechonk<T> {
+        self.raw.copy()
+    }
+}
+
+impl_access!([T: ?Sized] Vechonk<T>, "Vechonk");
+
+/// don't bother with destructors for now
+impl<T: ?Sized> Drop for Vechonk<T> {
+    fn drop(&mut self) {
+        // SAFETY: We as `Vechonk` do own the data, and it has the length `self.raw.cap`
+        unsafe {
+            RawVechonk::<T>::dealloc(self.raw.cap, self.raw.ptr.as_ptr());
+        }
+    }
+}
+
+impl<T: ?Sized> IntoIterator for Vechonk<T> {
+    type Item = Box<T>;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+// default trait impls
+
+impl<T: ?Sized> Default for Vechonk<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PartialEq for Vechonk<T>
+where
+    T: ?Sized + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for Vechonk<T> where T: ?Sized + PartialEq + Eq {}
+
+impl<T> PartialOrd for Vechonk<T>
+where
+    T: ?Sized + PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // see core::slice::cmp::SlicePartialOrd::partial_compare
+
+        let len = cmp::min(self.len(), other.len());
+        for i in 0..len {
+            // SAFETY: We did the bounds check above
+            let ordering = unsafe { self.get_unchecked(i).partial_cmp(other.get_unchecked(i)) };
+
+            match ordering {
+                Some(Ordering::Equal) => {}
+                non_eq => return non_eq,
+            }
+        }
+
+        self.len().partial_cmp(&other.len())
+    }
+}
+
+impl<T> Ord for Vechonk<T>
+where
+    T: ?Sized + PartialOrd + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // see core::slice::cmp::SliceOrd::compare
+
+        let len = cmp::min(self.len(), other.len());
+
+        for i in 0..len {
+            // SAFETY: We did the bounds check above
+            let ordering = unsafe { self.get_unchecked(i).cmp(other.get_unchecked(i)) };
+
+            match ordering {
+                Ordering::Equal => {}
+                non_eq => return non_eq,
+            }
+        }
+
+        self.len().cmp(&other.len())
+    }
+}
+
+impl<T> Hash for Vechonk<T>
+where
+    T: ?Sized + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.iter().for_each(|elem| elem.hash(state))
+    }
+}
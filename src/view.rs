@@ -0,0 +1,401 @@
+//! A stable binary image of a [`Vechonk<str>`] or [`Vechonk<[T]>`](Vechonk) of plain old data,
+//! that can be read back without copying
+
+use crate::Vechonk;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::{fmt, mem, slice, str};
+
+const MAGIC: [u8; 4] = *b"VCHK";
+const VERSION: u8 = 1;
+const HEADER_SIZE: usize = 32;
+const DATA_ALIGN: usize = 16;
+
+#[cfg(target_endian = "little")]
+const NATIVE_ENDIAN: u8 = 1;
+#[cfg(target_endian = "big")]
+const NATIVE_ENDIAN: u8 = 2;
+
+const KIND_STR: u8 = 0;
+const KIND_SLICE: u8 = 1;
+
+mod private {
+    pub trait Sealed {
+        const KIND: u8;
+        const ITEM_SIZE: usize;
+        const ITEM_ALIGN: usize;
+
+        fn elem_bytes(&self) -> &[u8];
+
+        /// Whether `bytes` is a valid element. `bytes` is aligned to `Self::ITEM_ALIGN`
+        fn is_valid(bytes: &[u8]) -> bool;
+
+        /// # Safety
+        /// `bytes` must be valid, see `Self::is_valid`
+        unsafe fn from_elem_bytes(bytes: &[u8]) -> &Self;
+    }
+}
+
+/// Plain old data, that can be stored in a [`Vechonk<[T]>`](Vechonk) image
+///
+/// Zero sized types can't be stored, since the length of an element is computed from its size in bytes.
+/// Using one fails to compile:
+///
+/// ```compile_fail
+/// # use vechonk::{Pod, Vechonk};
+/// #[derive(Clone, Copy)]
+/// struct Empty;
+///
+/// // SAFETY: There are no bytes, so there is no padding, and the only value is valid
+/// unsafe impl Pod for Empty {}
+///
+/// let chonk: Vechonk<[Empty]> = Vechonk::new();
+/// chonk.to_bytes();
+/// ```
+///
+/// # Safety
+/// The type must not contain any padding bytes or pointers, and every bit pattern must be a valid value
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(
+            // SAFETY: Primitive integers and floats don't have padding, and every bit pattern is valid
+            unsafe impl Pod for $ty {}
+        )*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// An element type that has a stable binary representation, see [`Vechonk::to_bytes`] and [`VechonkView`]
+///
+/// This is implemented for `str` and `[T]` where `T` is [`Pod`]
+pub trait BytesElem: private::Sealed {}
+
+impl private::Sealed for str {
+    const KIND: u8 = KIND_STR;
+    const ITEM_SIZE: usize = 1;
+    const ITEM_ALIGN: usize = 1;
+
+    fn elem_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn is_valid(bytes: &[u8]) -> bool {
+        str::from_utf8(bytes).is_ok()
+    }
+
+    unsafe fn from_elem_bytes(bytes: &[u8]) -> &Self {
+        // SAFETY: The caller has checked that the bytes are valid UTF-8
+        unsafe { str::from_utf8_unchecked(bytes) }
+    }
+}
+
+impl BytesElem for str {}
+
+impl<T: Pod> private::Sealed for [T] {
+    const KIND: u8 = KIND_SLICE;
+    const ITEM_SIZE: usize = {
+        // the length of an element is its size divided by this
+        assert!(mem::size_of::<T>() != 0, "zero sized types can't be stored");
+        mem::size_of::<T>()
+    };
+    const ITEM_ALIGN: usize = mem::align_of::<T>();
+
+    fn elem_bytes(&self) -> &[u8] {
+        // SAFETY: `T` is `Pod`, so it doesn't contain any padding bytes that could be uninitialized
+        unsafe { slice::from_raw_parts(self.as_ptr().cast::<u8>(), mem::size_of_val(self)) }
+    }
+
+    fn is_valid(bytes: &[u8]) -> bool {
+        bytes.len() % Self::ITEM_SIZE == 0
+    }
+
+    unsafe fn from_elem_bytes(bytes: &[u8]) -> &Self {
+        // SAFETY: The caller has checked that the length is a multiple of the size of `T` and that the bytes
+        //         are aligned. Every bit pattern is a valid `T`, since it's `Pod`
+        unsafe { slice::from_raw_parts(bytes.as_ptr().cast::<T>(), bytes.len() / Self::ITEM_SIZE) }
+    }
+}
+
+impl<T: Pod> BytesElem for [T] {}
+
+impl<T: ?Sized + BytesElem> Vechonk<T> {
+    /// Writes the `Vechonk` into a binary image, that can be read back without copying using
+    /// [`VechonkView::from_bytes`]. See [`VechonkView`] for the format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let table_size = mem::size_of::<u64>() * (self.len() + 1);
+        let data_start = data_start(table_size);
+        let data_size = self
+            .iter()
+            .map(|elem| elem.elem_bytes().len())
+            .sum::<usize>();
+
+        let mut bytes = Vec::with_capacity(data_start + data_size);
+
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&[VERSION, NATIVE_ENDIAN, T::KIND, 0]);
+        bytes.extend_from_slice(&(T::ITEM_SIZE as u32).to_ne_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&(self.len() as u64).to_ne_bytes());
+        bytes.extend_from_slice(&(data_size as u64).to_ne_bytes());
+
+        let mut offset = 0;
+        for elem in self.iter() {
+            bytes.extend_from_slice(&(offset as u64).to_ne_bytes());
+            offset += elem.elem_bytes().len();
+        }
+        bytes.extend_from_slice(&(offset as u64).to_ne_bytes());
+
+        bytes.resize(data_start, 0);
+
+        for elem in self.iter() {
+            bytes.extend_from_slice(elem.elem_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// The reasons why an image can't be read by [`VechonkView::from_bytes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// The image ends before all the data declared in the header
+    TooShort,
+    /// The image doesn't start with the magic bytes
+    BadMagic,
+    /// The image has a version that isn't supported
+    UnsupportedVersion(u8),
+    /// The image was written on a machine with a different endianness
+    WrongEndianness,
+    /// The image contains a different element type
+    WrongElementType,
+    /// The data section of the image isn't aligned for the element type
+    Misaligned,
+    /// The offsets are not monotonic, out of bounds, or don't fit the element type
+    InvalidOffsets,
+    /// An element is not valid, for example because it's not UTF-8
+    InvalidElement,
+    /// There are bytes after the end of the data section
+    TrailingBytes,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort => f.write_str("image is too short"),
+            Self::BadMagic => f.write_str("image doesn't start with the magic bytes"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            Self::WrongEndianness => f.write_str("image has the wrong endianness"),
+            Self::WrongElementType => f.write_str("image contains a different element type"),
+            Self::Misaligned => f.write_str("data section is not aligned for the element type"),
+            Self::InvalidOffsets => f.write_str("element offsets are invalid"),
+            Self::InvalidElement => f.write_str("element is invalid"),
+            Self::TrailingBytes => f.write_str("image has trailing bytes"),
+        }
+    }
+}
+
+/// A read-only view into an image created by [`Vechonk::to_bytes`], that doesn't copy any elements
+///
+/// The image is validated once in [`VechonkView::from_bytes`], so accessing the elements is cheap afterwards
+///
+/// # Format
+///
+/// All integers are stored in the native endianness of the machine that wrote the image, which is recorded
+/// in the header. Images with a different endianness are rejected instead of converted, since that would
+/// require copying.
+///
+/// ```txt
+/// offset      size          content
+/// 0           4             magic bytes, "VCHK"
+/// 4           1             version, currently 1
+/// 5           1             endianness, 1 = little, 2 = big
+/// 6           1             element kind, 0 = str, 1 = slice
+/// 7           1             reserved, 0
+/// 8           4             size of a single item (1 for str, size_of::<T>() for [T]) as u32
+/// 12          4             reserved, 0
+/// 16          8             number of elements `len` as u64
+/// 24          8             size of the data section in bytes as u64
+/// 32          8 * (len + 1) start offset of each element relative to the data section and the end of the last one
+/// ...         0 or 8        padding, so that the data section starts at a multiple of 16
+/// ...         rest          data section, the elements without any padding in between
+/// ```
+pub struct VechonkView<'a, T: ?Sized> {
+    len: usize,
+    /// The offset table, `len + 1` native endian `u64`
+    offsets: &'a [u8],
+    data: &'a [u8],
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T: ?Sized + BytesElem> VechonkView<'a, T> {
+    /// Validates an image and creates a view into it
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, FormatError> {
+        let header = bytes.get(..HEADER_SIZE).ok_or(FormatError::TooShort)?;
+
+        if header[0..4] != MAGIC {
+            return Err(FormatError::BadMagic);
+        }
+        if header[4] != VERSION {
+            return Err(FormatError::UnsupportedVersion(header[4]));
+        }
+        if header[5] != NATIVE_ENDIAN {
+            return Err(FormatError::WrongEndianness);
+        }
+        if header[6] != T::KIND || read_u32(&header[8..12]) as usize != T::ITEM_SIZE {
+            return Err(FormatError::WrongElementType);
+        }
+
+        let len = usize::try_from(read_u64(&header[16..24])).map_err(|_| FormatError::TooShort)?;
+        let data_size =
+            usize::try_from(read_u64(&header[24..32])).map_err(|_| FormatError::TooShort)?;
+
+        let table_size = len
+            .checked_add(1)
+            .and_then(|entries| entries.checked_mul(mem::size_of::<u64>()))
+            .ok_or(FormatError::TooShort)?;
+        let table_end = HEADER_SIZE
+            .checked_add(table_size)
+            .ok_or(FormatError::TooShort)?;
+        let offsets = bytes
+            .get(HEADER_SIZE..table_end)
+            .ok_or(FormatError::TooShort)?;
+
+        let data_start = data_start(table_size);
+        let data_end = data_start
+            .checked_add(data_size)
+            .ok_or(FormatError::TooShort)?;
+        let data = bytes
+            .get(data_start..data_end)
+            .ok_or(FormatError::TooShort)?;
+
+        if bytes.len() != data_end {
+            return Err(FormatError::TrailingBytes);
+        }
+
+        if data.as_ptr().align_offset(T::ITEM_ALIGN) != 0 {
+            return Err(FormatError::Misaligned);
+        }
+
+        let view = Self {
+            len,
+            offsets,
+            data,
+            _marker: PhantomData,
+        };
+
+        if view.offset(0) != 0 || view.offset(len) != data_size as u64 {
+            return Err(FormatError::InvalidOffsets);
+        }
+
+        for index in 0..len {
+            let (start, end) = (view.offset(index), view.offset(index + 1));
+
+            if start > end || end > data_size as u64 || start % T::ITEM_SIZE as u64 != 0 {
+                return Err(FormatError::InvalidOffsets);
+            }
+
+            if !T::is_valid(&data[start as usize..end as usize]) {
+                return Err(FormatError::InvalidElement);
+            }
+        }
+
+        Ok(view)
+    }
+
+    /// The amount of elements in the image, O(1)
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the image is empty, O(1)
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to an element at the index. Returns `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len {
+            // SAFETY: The index has been checked above
+            unsafe { Some(self.get_unchecked(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// # Safety
+    /// The index must be in bounds
+    pub unsafe fn get_unchecked(&self, index: usize) -> &'a T {
+        let start = self.offset(index) as usize;
+        let end = self.offset(index + 1) as usize;
+
+        // SAFETY: The offsets have been validated in `Self::from_bytes`, so they are in bounds
+        //         and the element is valid
+        unsafe { T::from_elem_bytes(self.data.get_unchecked(start..end)) }
+    }
+
+    /// An iterator over the elements yielding shared references
+    pub fn iter(&self) -> ViewIter<'a, T> {
+        ViewIter {
+            view: VechonkView {
+                len: self.len,
+                offsets: self.offsets,
+                data: self.data,
+                _marker: PhantomData,
+            },
+            current_index: 0,
+        }
+    }
+
+    /// Reads an entry of the offset table, `index` must be `<= self.len`
+    fn offset(&self, index: usize) -> u64 {
+        let start = index * mem::size_of::<u64>();
+        read_u64(&self.offsets[start..start + mem::size_of::<u64>()])
+    }
+}
+
+/// An iterator over the elements of a [`VechonkView`]
+pub struct ViewIter<'a, T: ?Sized> {
+    view: VechonkView<'a, T>,
+    current_index: usize,
+}
+
+impl<'a, T: ?Sized + BytesElem> Iterator for ViewIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem = self.view.get(self.current_index)?;
+
+        self.current_index += 1;
+
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.view.len - self.current_index;
+
+        (count, Some(count))
+    }
+}
+
+impl<'a, T: ?Sized + BytesElem> ExactSizeIterator for ViewIter<'a, T> {
+    fn len(&self) -> usize {
+        self.view.len - self.current_index
+    }
+}
+
+/// The start of the data section for an offset table of `table_size` bytes
+const fn data_start(table_size: usize) -> usize {
+    let unaligned = HEADER_SIZE + table_size;
+    unaligned + (DATA_ALIGN - unaligned % DATA_ALIGN) % DATA_ALIGN
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_ne_bytes(bytes.try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_ne_bytes(bytes.try_into().unwrap())
+}
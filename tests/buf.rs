@@ -0,0 +1,70 @@
+//! `VechonkBuf` is the only part that's available without `alloc`, so these tests only use what it has
+//! without it, and also run with `--no-default-features`
+
+use core::mem::MaybeUninit;
+use vechonk::VechonkBuf;
+
+#[test]
+fn push_pop_with() {
+    let mut buf = [MaybeUninit::uninit(); 128];
+    let mut chonk = VechonkBuf::<str>::new(&mut buf);
+
+    chonk.try_push_str("hello").unwrap();
+    chonk.try_push_str("uwu").unwrap();
+    let free = chonk.bytes_free();
+
+    assert_eq!(chonk.pop_with(|s| s.len()), Some(3));
+    assert_eq!(chonk.len(), 1);
+    assert!(chonk.bytes_free() > free);
+
+    chonk.try_push_str("owo").unwrap();
+    assert_eq!(chonk.bytes_free(), free);
+    assert!(chonk.iter().eq(["hello", "owo"]));
+
+    chonk.pop_with(|_| ()).unwrap();
+    chonk.pop_with(|_| ()).unwrap();
+    assert_eq!(chonk.pop_with(|_| ()), None);
+    assert_eq!(chonk.bytes_free(), chonk.capacity());
+}
+
+#[test]
+fn truncate_reuses_space() {
+    let mut buf = [MaybeUninit::uninit(); 256];
+    let mut chonk = VechonkBuf::<[u16]>::new(&mut buf);
+
+    chonk.try_push_slice(&[1, 2, 3]).unwrap();
+    let free = chonk.bytes_free();
+    while chonk.try_push_slice(&[4, 5]).is_ok() {}
+
+    chonk.truncate(5);
+    assert_eq!(chonk.len(), 5);
+
+    chonk.truncate(1);
+    assert_eq!(chonk.bytes_free(), free);
+    assert_eq!(&chonk[0], &[1, 2, 3]);
+
+    // truncating to a bigger length does nothing
+    chonk.truncate(10);
+    assert_eq!(chonk.len(), 1);
+}
+
+#[test]
+fn values_and_mutation() {
+    let mut buf = [MaybeUninit::uninit(); 128];
+    let mut chonk = VechonkBuf::<dyn core::fmt::Debug>::new(&mut buf);
+
+    chonk.try_push_value(1_u8).unwrap();
+    chonk.try_push_value([2_u64, 3]).unwrap();
+    chonk.try_push_value(()).unwrap();
+
+    assert_eq!(format!("{:?}", &chonk[1]), "[2, 3]");
+    assert_eq!(format!("{:?}", chonk.get(2).unwrap()), "()");
+    assert!(chonk.get_mut(3).is_none());
+
+    let mut slices = [MaybeUninit::uninit(); 64];
+    let mut slices = VechonkBuf::<[u8]>::new(&mut slices);
+    slices.try_push_slice(&[1, 2]).unwrap();
+    slices.iter_mut().for_each(|slice| slice[0] = 10);
+    slices[0][1] = 20;
+    assert_eq!(&slices[0], &[10, 20]);
+}